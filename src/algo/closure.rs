@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::normalize_predicate;
+use super::PredicateSet;
+use crate::constants::property::obo_in_owl;
+use crate::model::BasicPropertyValue;
+use crate::model::Edge;
+use crate::model::Graph;
+use crate::model::Meta;
+
+/// A `(sub, pred, obj)` triple borrowed from a graph.
+pub type Triple<'a> = (&'a str, &'a str, &'a str);
+
+/// The transitive closure of a graph over a set of predicates.
+///
+/// Entailed triples are computed with the following rules:
+/// * `is_a` is transitive, and any relationship propagates over `is_a`:
+///   `x R y, y is_a z` and `x is_a y, y R z` both entail `x R z`.
+/// * a property declared with `is_transitive: true` in its node metadata,
+///   or given to [`Closure::with_transitive`], is transitive: `x R y, y R z`
+///   entails `x R z`.
+/// * a `PropertyChainAxiom` `R <- R1 o ... o Rn` entails `x R z` from
+///   `x R1 y1, ..., yn-1 Rn z`.
+///
+/// Triples are returned sorted by subject, predicate and object. Reflexive
+/// triples, which are only entailed by cycles, are never returned.
+///
+/// The `is_transitive` metadata is only written by the `IntoGraph`
+/// conversion of this crate, as an `oboInOwl:is_transitive` property value.
+/// Graphs exported by other tools, such as ROBOT, do not declare
+/// transitive properties, so only `is_a` is transitive in their closure
+/// unless the transitive predicates are given explicitly.
+#[derive(Clone, Debug)]
+pub struct Closure<'a> {
+    id: &'a str,
+    triples: Vec<(Triple<'a>, bool)>,
+}
+
+impl<'a> Closure<'a> {
+    /// Compute the closure of the given graph over the given predicates.
+    ///
+    /// Edges with other predicates are still used in the computation when
+    /// they are part of a property chain entailing one of the predicates.
+    pub fn new(graph: &'a Graph, preds: &PredicateSet) -> Self {
+        Self::with_transitive(graph, preds, &PredicateSet::new())
+    }
+
+    /// Compute the closure of the given graph, with additional transitive predicates.
+    ///
+    /// The predicates in `transitive` are transitive in addition to the
+    /// ones declared transitive in the graph.
+    pub fn with_transitive(
+        graph: &'a Graph,
+        preds: &PredicateSet,
+        transitive: &PredicateSet,
+    ) -> Self {
+        Reasoner::new(graph, preds, transitive).saturate()
+    }
+
+    /// Iterate over all the entailed triples, asserted or inferred.
+    pub fn triples(&self) -> impl Iterator<Item = Triple<'a>> + '_ {
+        self.triples.iter().map(|&(t, _)| t)
+    }
+
+    /// Iterate over the entailed triples that are not asserted in the graph.
+    pub fn inferred(&self) -> impl Iterator<Item = Triple<'a>> + '_ {
+        self.triples.iter().filter(|(_, i)| *i).map(|&(t, _)| t)
+    }
+
+    /// Create a graph containing an edge for each inferred triple.
+    ///
+    /// The graph has the identifier of the source graph, and each edge is
+    /// annotated with `oboInOwl:is_inferred` set to `true`.
+    pub fn to_graph(&self) -> Graph {
+        let edges = self
+            .inferred()
+            .map(|(sub, pred, obj)| Edge {
+                sub: sub.to_string(),
+                pred: pred.to_string(),
+                obj: obj.to_string(),
+                meta: Some(Box::new(Meta {
+                    basic_property_values: vec![BasicPropertyValue::new(
+                        obo_in_owl::IS_INFERRED.to_string(),
+                        true.to_string(),
+                    )],
+                    ..Default::default()
+                })),
+                extra: Default::default(),
+            })
+            .collect();
+        Graph {
+            nodes: Vec::new(),
+            edges,
+            id: self.id.to_string(),
+            label: None,
+            meta: None,
+            equivalent_nodes_sets: Vec::new(),
+            logical_definition_axioms: Vec::new(),
+            domain_range_axioms: Vec::new(),
+            property_chain_axioms: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Compute the transitive closure of a graph over the given predicates.
+///
+/// See [`Closure`] for the rules used to entail new triples.
+pub fn closure<'a>(graph: &'a Graph, preds: &PredicateSet) -> Closure<'a> {
+    Closure::new(graph, preds)
+}
+
+// ---------------------------------------------------------------------------
+
+/// A forward-chaining reasoner using interned predicates.
+struct Reasoner<'a> {
+    graph: &'a Graph,
+    /// The normalized predicates, mapped to their index.
+    indices: HashMap<String, usize>,
+    /// The name of each predicate, as first found in the graph.
+    names: Vec<&'a str>,
+    /// Whether each predicate should be part of the closure.
+    output: Vec<bool>,
+    /// Whether each predicate is transitive.
+    transitive: Vec<bool>,
+    /// The property chains, as a result and a list of components.
+    chains: Vec<(usize, Vec<usize>)>,
+    facts: HashSet<(&'a str, usize, &'a str)>,
+    asserted: HashSet<(&'a str, usize, &'a str)>,
+    outgoing: HashMap<&'a str, Vec<(usize, &'a str)>>,
+    incoming: HashMap<&'a str, Vec<(&'a str, usize)>>,
+    queue: Vec<(&'a str, usize, &'a str)>,
+}
+
+const IS_A: usize = 0;
+
+impl<'a> Reasoner<'a> {
+    fn new(graph: &'a Graph, preds: &PredicateSet, transitive: &PredicateSet) -> Self {
+        let mut reasoner = Reasoner {
+            graph,
+            indices: HashMap::new(),
+            names: Vec::new(),
+            output: Vec::new(),
+            transitive: Vec::new(),
+            chains: Vec::new(),
+            facts: HashSet::new(),
+            asserted: HashSet::new(),
+            outgoing: HashMap::new(),
+            incoming: HashMap::new(),
+            queue: Vec::new(),
+        };
+
+        // the subclass relationship is always needed for propagation
+        let is_a = graph
+            .edges
+            .iter()
+            .map(|e| e.pred.as_str())
+            .find(|&p| normalize_predicate(p) == "is_a")
+            .unwrap_or("is_a");
+        reasoner.intern(is_a);
+
+        // collect the predicates needed to entail the requested ones
+        let mut relevant = preds.clone();
+        relevant.insert("is_a");
+        loop {
+            let mut changed = false;
+            for pca in graph.property_chain_axioms.iter() {
+                if relevant.contains(&pca.predicate_id) {
+                    for pred in pca.chain_predicate_ids.iter() {
+                        changed |= relevant.insert(pred);
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        for edge in graph.edges.iter().filter(|e| relevant.contains(&e.pred)) {
+            let pred = reasoner.intern(&edge.pred);
+            reasoner.asserted.insert((&edge.sub, pred, &edge.obj));
+        }
+        for pca in graph.property_chain_axioms.iter() {
+            if relevant.contains(&pca.predicate_id) && !pca.chain_predicate_ids.is_empty() {
+                let result = reasoner.intern(&pca.predicate_id);
+                let chain = pca
+                    .chain_predicate_ids
+                    .iter()
+                    .map(|p| reasoner.intern(p))
+                    .collect();
+                reasoner.chains.push((result, chain));
+            }
+        }
+        for node in graph.nodes.iter() {
+            let transitive = node.meta.as_ref().is_some_and(|meta| {
+                meta.basic_property_values
+                    .iter()
+                    .any(|pv| pv.pred == obo_in_owl::IS_TRANSITIVE && pv.val == "true")
+            });
+            if transitive {
+                if let Some(&i) = reasoner.indices.get(normalize_predicate(&node.id).as_ref()) {
+                    reasoner.transitive[i] = true;
+                }
+            }
+        }
+        reasoner.transitive[IS_A] = true;
+        for (i, name) in reasoner.names.iter().enumerate() {
+            reasoner.output[i] = preds.contains(name);
+            reasoner.transitive[i] |= transitive.contains(name);
+        }
+
+        reasoner
+    }
+
+    fn intern(&mut self, pred: &'a str) -> usize {
+        let key = normalize_predicate(pred).into_owned();
+        if let Some(&i) = self.indices.get(&key) {
+            return i;
+        }
+        let i = self.names.len();
+        self.indices.insert(key, i);
+        self.names.push(pred);
+        self.output.push(false);
+        self.transitive.push(false);
+        i
+    }
+
+    fn insert(&mut self, sub: &'a str, pred: usize, obj: &'a str) {
+        if self.facts.insert((sub, pred, obj)) {
+            self.outgoing.entry(sub).or_default().push((pred, obj));
+            self.incoming.entry(obj).or_default().push((sub, pred));
+            self.queue.push((sub, pred, obj));
+        }
+    }
+
+    fn outgoing(&self, sub: &str) -> Vec<(usize, &'a str)> {
+        self.outgoing.get(sub).cloned().unwrap_or_default()
+    }
+
+    fn incoming(&self, obj: &str) -> Vec<(&'a str, usize)> {
+        self.incoming.get(obj).cloned().unwrap_or_default()
+    }
+
+    /// Follow the edges with the given predicate from a set of nodes.
+    fn step(&self, nodes: HashSet<&'a str>, pred: usize, forward: bool) -> HashSet<&'a str> {
+        let mut next = HashSet::new();
+        for node in nodes {
+            if forward {
+                let edges = self.outgoing.get(node).map(Vec::as_slice).unwrap_or(&[]);
+                next.extend(edges.iter().filter(|(p, _)| *p == pred).map(|&(_, o)| o));
+            } else {
+                let edges = self.incoming.get(node).map(Vec::as_slice).unwrap_or(&[]);
+                next.extend(edges.iter().filter(|(_, p)| *p == pred).map(|&(s, _)| s));
+            }
+        }
+        next
+    }
+
+    fn saturate(mut self) -> Closure<'a> {
+        let mut asserted = self.asserted.iter().copied().collect::<Vec<_>>();
+        asserted.sort_unstable();
+        for (sub, pred, obj) in asserted {
+            self.insert(sub, pred, obj);
+        }
+
+        while let Some((x, p, y)) = self.queue.pop() {
+            if p == IS_A {
+                // x is_a y, y R z => x R z
+                for (q, z) in self.outgoing(y) {
+                    self.insert(x, q, z);
+                }
+                // w R x, x is_a y => w R y
+                for (w, q) in self.incoming(x) {
+                    self.insert(w, q, y);
+                }
+            } else {
+                // w is_a x, x R y => w R y
+                for (w, q) in self.incoming(x) {
+                    if q == IS_A {
+                        self.insert(w, p, y);
+                    }
+                }
+                // x R y, y is_a z => x R z
+                for (q, z) in self.outgoing(y) {
+                    if q == IS_A {
+                        self.insert(x, p, z);
+                    }
+                }
+                if self.transitive[p] {
+                    for (q, z) in self.outgoing(y) {
+                        if q == p {
+                            self.insert(x, p, z);
+                        }
+                    }
+                    for (w, q) in self.incoming(x) {
+                        if q == p {
+                            self.insert(w, p, y);
+                        }
+                    }
+                }
+            }
+
+            for c in 0..self.chains.len() {
+                for k in 0..self.chains[c].1.len() {
+                    if self.chains[c].1[k] != p {
+                        continue;
+                    }
+                    let (result, chain) = self.chains[c].clone();
+                    let mut starts = HashSet::new();
+                    starts.insert(x);
+                    for &pred in chain[..k].iter().rev() {
+                        starts = self.step(starts, pred, false);
+                    }
+                    let mut ends = HashSet::new();
+                    ends.insert(y);
+                    for &pred in chain[k + 1..].iter() {
+                        ends = self.step(ends, pred, true);
+                    }
+                    for &s in starts.iter() {
+                        for &e in ends.iter() {
+                            self.insert(s, result, e);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut triples = self
+            .facts
+            .iter()
+            .filter(|(s, p, o)| self.output[*p] && s != o)
+            .map(|&(s, p, o)| ((s, self.names[p], o), !self.asserted.contains(&(s, p, o))))
+            .collect::<Vec<_>>();
+        triples.sort_unstable();
+        Closure {
+            id: &self.graph.id,
+            triples,
+        }
+    }
+}
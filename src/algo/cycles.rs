@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::model::Edge;
+use crate::model::Graph;
+
+/// A cycle in the hierarchy of a graph.
+///
+/// A cycle is a strongly connected component of more than one node, i.e.
+/// a maximal set of nodes which are all ancestors of each other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<'a> {
+    /// The nodes of the cycle with their label, sorted by identifier.
+    pub nodes: Vec<(&'a str, Option<&'a str>)>,
+    /// The edges between the nodes of the cycle, in the order of the graph.
+    pub edges: Vec<&'a Edge>,
+}
+
+impl<'a> Cycle<'a> {
+    /// Iterate over the identifiers of the nodes of the cycle.
+    pub fn ids(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.nodes.iter().map(|(id, _)| *id)
+    }
+}
+
+impl<'a> Hierarchy<'a> {
+    /// Get the strongly connected components of more than one node.
+    ///
+    /// Components are found with an iterative version of Tarjan's
+    /// algorithm, so that deep hierarchies cannot overflow the stack. The
+    /// nodes of each component are sorted by identifier, and components
+    /// are sorted by their first node. Self-loops are not reported.
+    pub fn cycles(&self) -> Vec<Vec<&'a str>> {
+        let mut index: HashMap<&'a str, usize> = HashMap::new();
+        let mut lowlink: HashMap<&'a str, usize> = HashMap::new();
+        let mut on_stack = HashSet::new();
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+
+        for root in self.nodes() {
+            if index.contains_key(root) {
+                continue;
+            }
+
+            // each frame holds a node and the position of its next parent
+            let mut frames = vec![(root, 0)];
+            index.insert(root, index.len());
+            lowlink.insert(root, index[root]);
+            on_stack.insert(root);
+            stack.push(root);
+
+            while let Some(&mut (node, ref mut next)) = frames.last_mut() {
+                if let Some(&parent) = self.parents(node).get(*next) {
+                    *next += 1;
+                    if !index.contains_key(parent) {
+                        index.insert(parent, index.len());
+                        lowlink.insert(parent, index[parent]);
+                        on_stack.insert(parent);
+                        stack.push(parent);
+                        frames.push((parent, 0));
+                    } else if on_stack.contains(parent) {
+                        let low = lowlink[node].min(index[parent]);
+                        lowlink.insert(node, low);
+                    }
+                    continue;
+                }
+
+                frames.pop();
+                if let Some(&(caller, _)) = frames.last() {
+                    let low = lowlink[caller].min(lowlink[node]);
+                    lowlink.insert(caller, low);
+                }
+                if lowlink[node] == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack.remove(member);
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 {
+                        component.sort_unstable();
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components.sort_unstable();
+        components
+    }
+}
+
+/// Find the cycles of a graph over the given predicates.
+///
+/// Edges with a `true` value for any of the `skip` metadata properties are
+/// ignored, e.g. use `obo_in_owl::IS_INFERRED` to only check the asserted
+/// edges of a graph produced by [`Closure::to_graph`](super::Closure::to_graph).
+pub fn cycles<'a>(graph: &'a Graph, preds: &PredicateSet, skip: &[&str]) -> Vec<Cycle<'a>> {
+    let marked = |edge: &Edge| {
+        edge.meta.as_ref().is_some_and(|meta| {
+            meta.basic_property_values
+                .iter()
+                .any(|pv| pv.val == "true" && skip.contains(&pv.pred.as_str()))
+        })
+    };
+    let edges = graph
+        .edges
+        .iter()
+        .filter(|e| preds.contains(&e.pred) && !marked(e))
+        .collect::<Vec<_>>();
+
+    let mut labels = HashMap::new();
+    for node in graph.nodes.iter() {
+        labels
+            .entry(node.id.as_str())
+            .or_insert_with(|| node.label.as_deref());
+    }
+
+    Hierarchy::from_edges(edges.iter().copied())
+        .cycles()
+        .into_iter()
+        .map(|component| {
+            let members = component.iter().copied().collect::<HashSet<_>>();
+            Cycle {
+                edges: edges
+                    .iter()
+                    .copied()
+                    .filter(|e| {
+                        members.contains(e.sub.as_str()) && members.contains(e.obj.as_str())
+                    })
+                    .collect(),
+                nodes: component
+                    .into_iter()
+                    .map(|id| (id, labels.get(id).copied().flatten()))
+                    .collect(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::model::Edge;
+use crate::model::Graph;
+
+/// A path between two nodes going through a common ancestor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<'a> {
+    /// The common ancestor at the top of the path.
+    pub ancestor: &'a str,
+    /// The nodes of the path, from the first node to the second one.
+    pub nodes: Vec<&'a str>,
+    /// The edges of the path, from the first node to the second one.
+    ///
+    /// Edges are returned as they appear in the graph, so the edges of the
+    /// second half of the path go from the second node to the ancestor.
+    pub edges: Vec<&'a Edge>,
+}
+
+impl<'a> Path<'a> {
+    /// Get the length of the path, in number of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Check whether the path is empty, i.e. both nodes are the same.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl<'a> Hierarchy<'a> {
+    /// Get the common ancestors of two nodes, sorted by identifier.
+    ///
+    /// Each node is considered to be one of its own ancestors, so that the
+    /// common ancestors of a node and one of its ancestors include the
+    /// latter.
+    pub fn common_ancestors(&self, a: &str, b: &str) -> Vec<&'a str> {
+        let da = self.distances_up(a);
+        let db = self.distances_up(b);
+        let mut common = da
+            .keys()
+            .filter(|id| db.contains_key(*id))
+            .copied()
+            .collect::<Vec<_>>();
+        common.sort_unstable();
+        common
+    }
+
+    /// Get the lowest common ancestors of two nodes, sorted by identifier.
+    ///
+    /// A lowest common ancestor is a common ancestor which is not an
+    /// ancestor of any other common ancestor. Since the hierarchy is a DAG
+    /// and not a tree, two nodes can have several lowest common ancestors.
+    /// Common ancestors in the same cycle are considered equally low.
+    pub fn lowest_common_ancestors(&self, a: &str, b: &str) -> Vec<&'a str> {
+        let common = self.common_ancestors(a, b);
+        let ancestors = common
+            .iter()
+            .map(|&c| {
+                (
+                    c,
+                    self.ancestors(c, None).into_iter().collect::<HashSet<_>>(),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        common
+            .iter()
+            .copied()
+            .filter(|c| {
+                !common
+                    .iter()
+                    .any(|d| d != c && ancestors[d].contains(c) && !ancestors[c].contains(d))
+            })
+            .collect()
+    }
+
+    /// Get the shortest path between two nodes through a common ancestor.
+    ///
+    /// Returns `None` if the nodes have no common ancestor. Ties between
+    /// paths of the same length are broken by ancestor identifier.
+    pub fn shortest_path(&self, a: &str, b: &str) -> Option<Path<'a>> {
+        let da = self.distances_up(a);
+        let db = self.distances_up(b);
+        let (&ancestor, _) = da
+            .iter()
+            .filter_map(|(id, x)| db.get(id).map(|y| (id, x + y)))
+            .min_by(|(id1, d1), (id2, d2)| d1.cmp(d2).then(id1.cmp(id2)))?;
+
+        let up = self.path_up(a, ancestor)?;
+        let down = self.path_up(b, ancestor)?;
+
+        let mut nodes = Vec::with_capacity(up.len() + down.len() + 1);
+        nodes.push(self.node(a)?);
+        nodes.extend(up.iter().map(|e| e.obj.as_str()));
+        nodes.extend(down.iter().rev().map(|e| e.sub.as_str()));
+
+        let mut edges = up;
+        edges.extend(down.into_iter().rev());
+        Some(Path {
+            ancestor,
+            nodes,
+            edges,
+        })
+    }
+
+    /// Compute the distance from a node to each of its ancestors.
+    fn distances_up(&self, id: &str) -> HashMap<&'a str, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        let start = match self.node(id) {
+            Some(start) => start,
+            None => return distances,
+        };
+        distances.insert(start, 0);
+        queue.push_back(start);
+        while let Some(node) = queue.pop_front() {
+            let d = distances[node] + 1;
+            for &parent in self.parents(node) {
+                if !distances.contains_key(parent) {
+                    distances.insert(parent, d);
+                    queue.push_back(parent);
+                }
+            }
+        }
+        distances
+    }
+}
+
+/// Get the lowest common ancestors of two nodes over the given predicates.
+///
+/// See [`Hierarchy::lowest_common_ancestors`] for details.
+pub fn lowest_common_ancestors<'a>(
+    graph: &'a Graph,
+    a: &str,
+    b: &str,
+    preds: &PredicateSet,
+) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).lowest_common_ancestors(a, b)
+}
+
+/// Get the shortest path between two nodes over the given predicates.
+///
+/// See [`Hierarchy::shortest_path`] for details.
+pub fn shortest_path<'a>(
+    graph: &'a Graph,
+    a: &str,
+    b: &str,
+    preds: &PredicateSet,
+) -> Option<Path<'a>> {
+    Hierarchy::new(graph, preds).shortest_path(a, b)
+}
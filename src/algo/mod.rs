@@ -0,0 +1,214 @@
+//! Graph algorithms over the hierarchy of an OBO graph.
+//!
+//! The algorithms of this module only consider the edges of a [`Graph`]
+//! whose predicate belongs to a [`PredicateSet`], such as `is_a` and
+//! `part_of`, and build a [`Hierarchy`] from them, where the subject of an
+//! edge is a child of its object.
+
+mod closure;
+mod cycles;
+mod lca;
+mod reduction;
+mod similarity;
+mod topo;
+mod traverse;
+
+pub use self::closure::closure;
+pub use self::closure::Closure;
+pub use self::closure::Triple;
+pub use self::cycles::cycles;
+pub use self::cycles::Cycle;
+pub use self::lca::lowest_common_ancestors;
+pub use self::lca::shortest_path;
+pub use self::lca::Path;
+pub use self::reduction::transitive_reduction;
+pub use self::reduction::Reduction;
+pub use self::reduction::RedundantEdge;
+pub use self::similarity::Measure;
+pub use self::similarity::Similarity;
+pub use self::topo::depths;
+pub use self::topo::leaves;
+pub use self::topo::roots;
+pub use self::topo::topological_sort;
+pub use self::topo::Depth;
+pub use self::traverse::ancestors;
+pub use self::traverse::children;
+pub use self::traverse::descendants;
+pub use self::traverse::parents;
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::constants::property::rdfs;
+use crate::constants::uri;
+use crate::model::Edge;
+use crate::model::Graph;
+use crate::model::NodeType;
+
+/// Normalize an edge predicate so that equivalent predicates compare equal.
+///
+/// The subclass relationship, written `is_a`, `subClassOf`, `rdfs:subClassOf`
+/// or with its full IRI, is normalized to `is_a`. OBO IRIs such as
+/// `http://purl.obolibrary.org/obo/BFO_0000050` are normalized to their
+/// CURIE, `BFO:0000050`. Other predicates are left as they are.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// # use fastobo_graphs::algo::normalize_predicate;
+/// assert_eq!(normalize_predicate("rdfs:subClassOf"), "is_a");
+/// assert_eq!(normalize_predicate("http://purl.obolibrary.org/obo/BFO_0000050"), "BFO:0000050");
+/// assert_eq!(normalize_predicate("part_of"), "part_of");
+/// ```
+pub fn normalize_predicate(pred: &str) -> Cow<'_, str> {
+    match pred {
+        "is_a" | "subClassOf" | "rdfs:subClassOf" | rdfs::SUB_CLASS_OF => Cow::Borrowed("is_a"),
+        _ => match pred.strip_prefix(uri::OBO) {
+            Some(local) if !local.contains(['#', '/']) => match local.find('_') {
+                Some(i) if i > 0 => Cow::Owned(format!("{}:{}", &local[..i], &local[i + 1..])),
+                _ => Cow::Borrowed(pred),
+            },
+            _ => Cow::Borrowed(pred),
+        },
+    }
+}
+
+/// A set of edge predicates, compared after normalization.
+///
+/// Predicates can be given as CURIEs or as full IRIs, as they appear in
+/// [`Edge::pred`]; see [`normalize_predicate`] for the equivalences.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PredicateSet {
+    predicates: BTreeSet<String>,
+}
+
+impl PredicateSet {
+    /// Create a new empty predicate set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a predicate set containing only the subclass relationship.
+    pub fn is_a() -> Self {
+        Self::from_iter(&["is_a"])
+    }
+
+    /// Add a predicate to the set.
+    ///
+    /// Returns `false` if an equivalent predicate was already in the set.
+    pub fn insert(&mut self, pred: &str) -> bool {
+        self.predicates
+            .insert(normalize_predicate(pred).into_owned())
+    }
+
+    /// Check whether the set contains the given predicate.
+    pub fn contains(&self, pred: &str) -> bool {
+        self.predicates.contains(normalize_predicate(pred).as_ref())
+    }
+
+    /// Check whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.predicates.is_empty()
+    }
+
+    /// Iterate over the normalized predicates of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.predicates.iter().map(String::as_str)
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for PredicateSet {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for pred in iter {
+            self.insert(pred.as_ref());
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for PredicateSet {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// The hierarchy formed by the edges of a graph over a set of predicates.
+///
+/// Adjacency lists are sorted by node identifier, so that all the
+/// traversals are deterministic.
+#[derive(Clone, Debug, Default)]
+pub struct Hierarchy<'a> {
+    nodes: BTreeSet<&'a str>,
+    up: HashMap<&'a str, Vec<&'a Edge>>,
+    down: HashMap<&'a str, Vec<&'a Edge>>,
+    parents: HashMap<&'a str, Vec<&'a str>>,
+    children: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Hierarchy<'a> {
+    /// Build the hierarchy of the given graph over the given predicates.
+    ///
+    /// The `CLASS` nodes of the graph are part of the hierarchy even if
+    /// they have no edge over the given predicates.
+    pub fn new(graph: &'a Graph, preds: &PredicateSet) -> Self {
+        let mut hierarchy =
+            Self::from_edges(graph.edges.iter().filter(|e| preds.contains(&e.pred)));
+        let classes = graph.nodes.iter().filter(|n| n.ty == Some(NodeType::Class));
+        hierarchy.nodes.extend(classes.map(|n| n.id.as_str()));
+        hierarchy
+    }
+
+    /// Build a hierarchy from the given edges, regardless of their predicate.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Edge>,
+    {
+        let mut hierarchy = Self::default();
+        for edge in edges {
+            hierarchy.nodes.insert(&edge.sub);
+            hierarchy.nodes.insert(&edge.obj);
+            hierarchy.up.entry(&edge.sub).or_default().push(edge);
+            hierarchy.down.entry(&edge.obj).or_default().push(edge);
+        }
+        for edges in hierarchy.up.values_mut() {
+            edges.sort_by(|a, b| (&a.obj, &a.pred).cmp(&(&b.obj, &b.pred)));
+        }
+        for edges in hierarchy.down.values_mut() {
+            edges.sort_by(|a, b| (&a.sub, &a.pred).cmp(&(&b.sub, &b.pred)));
+        }
+        for (&id, edges) in hierarchy.up.iter() {
+            let mut parents = edges.iter().map(|e| e.obj.as_str()).collect::<Vec<_>>();
+            parents.dedup();
+            hierarchy.parents.insert(id, parents);
+        }
+        for (&id, edges) in hierarchy.down.iter() {
+            let mut children = edges.iter().map(|e| e.sub.as_str()).collect::<Vec<_>>();
+            children.dedup();
+            hierarchy.children.insert(id, children);
+        }
+        hierarchy
+    }
+
+    /// Iterate over the identifiers of all the nodes of the hierarchy, in order.
+    pub fn nodes(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.nodes.iter().copied()
+    }
+
+    /// Get the identifier of a node of the hierarchy, borrowed from the graph.
+    pub fn node(&self, id: &str) -> Option<&'a str> {
+        self.nodes.get(id).copied()
+    }
+
+    /// Get the edges with the given node as their subject.
+    pub fn edges_up(&self, id: &str) -> &[&'a Edge] {
+        self.up.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get the edges with the given node as their object.
+    pub fn edges_down(&self, id: &str) -> &[&'a Edge] {
+        self.down.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::normalize_predicate;
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::model::Edge;
+use crate::model::Graph;
+
+/// An asserted edge implied by other edges of the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RedundantEdge<'a> {
+    /// The position of the redundant edge in the edges of the graph.
+    pub index: usize,
+    /// The redundant edge.
+    pub edge: &'a Edge,
+    /// A path of other edges from the subject to the object of the edge.
+    ///
+    /// The path contains a single edge if the redundant edge is a duplicate.
+    pub path: Vec<&'a Edge>,
+}
+
+/// The transitive reduction of the hierarchy of a graph.
+///
+/// An edge from `x` to `y` is redundant when `y` can also be reached from
+/// `x` through a longer path, or when it duplicates another edge with the
+/// same subject, predicate and object. Since the reduction of a cyclic
+/// graph is not unique, edges are never made redundant by a path through
+/// a node in the same cycle as their subject or object, so that the
+/// reduced graph keeps the ancestors of every node.
+#[derive(Clone, Debug)]
+pub struct Reduction<'a> {
+    graph: &'a Graph,
+    preds: PredicateSet,
+    redundant: Vec<RedundantEdge<'a>>,
+}
+
+impl<'a> Reduction<'a> {
+    /// Reduce the edges over the given predicates, regardless of their predicate.
+    ///
+    /// An `is_a` edge can be made redundant by a path of `part_of` edges,
+    /// which is mostly useful to create a minimal graph to visualise.
+    pub fn new(graph: &'a Graph, preds: &PredicateSet) -> Self {
+        let edges = graph
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| preds.contains(&e.pred));
+        let mut redundant = HashMap::new();
+        reduce(edges.collect(), &mut redundant);
+        Self::with_redundant(graph, preds, redundant)
+    }
+
+    /// Reduce the edges of each of the given predicates separately.
+    ///
+    /// An edge can only be made redundant by a path of edges with the same
+    /// predicate, which is what is expected when linting a hierarchy.
+    pub fn by_predicate(graph: &'a Graph, preds: &PredicateSet) -> Self {
+        let mut groups: HashMap<_, Vec<(usize, &'a Edge)>> = HashMap::new();
+        for (i, edge) in graph.edges.iter().enumerate() {
+            if preds.contains(&edge.pred) {
+                groups
+                    .entry(normalize_predicate(&edge.pred))
+                    .or_default()
+                    .push((i, edge));
+            }
+        }
+        let mut redundant = HashMap::new();
+        for edges in groups.into_values() {
+            reduce(edges, &mut redundant);
+        }
+        Self::with_redundant(graph, preds, redundant)
+    }
+
+    fn with_redundant(
+        graph: &'a Graph,
+        preds: &PredicateSet,
+        mut redundant: HashMap<usize, Vec<&'a Edge>>,
+    ) -> Self {
+        let redundant = graph
+            .edges
+            .iter()
+            .enumerate()
+            .filter_map(|(index, edge)| {
+                redundant
+                    .remove(&index)
+                    .map(|path| RedundantEdge { index, edge, path })
+            })
+            .collect();
+        Self {
+            graph,
+            preds: preds.clone(),
+            redundant,
+        }
+    }
+
+    /// Get the redundant edges, in the order of the graph.
+    pub fn redundant(&self) -> &[RedundantEdge<'a>] {
+        &self.redundant
+    }
+
+    /// Check whether the edge at the given position in the graph is redundant.
+    pub fn is_redundant(&self, index: usize) -> bool {
+        self.redundant.iter().any(|r| r.index == index)
+    }
+
+    /// Iterate over the edges of the reduced hierarchy.
+    ///
+    /// Edges with a predicate outside of the reduced predicates are not
+    /// part of the hierarchy, and are not returned.
+    pub fn edges(&self) -> impl Iterator<Item = &'a Edge> + '_ {
+        let redundant = self.positions();
+        self.graph
+            .edges
+            .iter()
+            .enumerate()
+            .filter(move |(i, e)| self.preds.contains(&e.pred) && !redundant.contains(i))
+            .map(|(_, e)| e)
+    }
+
+    /// Create a copy of the graph without the redundant edges.
+    pub fn to_graph(&self) -> Graph {
+        let redundant = self.positions();
+        let mut graph = self.graph.clone();
+        graph.edges = self
+            .graph
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !redundant.contains(i))
+            .map(|(_, e)| e.clone())
+            .collect();
+        graph
+    }
+
+    fn positions(&self) -> HashSet<usize> {
+        self.redundant.iter().map(|r| r.index).collect()
+    }
+}
+
+/// Compute the transitive reduction of a graph over the given predicates.
+///
+/// See [`Reduction::new`] and [`Reduction::by_predicate`] for the two ways
+/// of handling several predicates.
+pub fn transitive_reduction<'a>(graph: &'a Graph, preds: &PredicateSet) -> Reduction<'a> {
+    Reduction::new(graph, preds)
+}
+
+/// Find the redundant edges of a hierarchy, with a path witnessing each.
+///
+/// Edges are given with their position in the graph, which is used as the
+/// key of the redundant edges.
+fn reduce<'a>(edges: Vec<(usize, &'a Edge)>, redundant: &mut HashMap<usize, Vec<&'a Edge>>) {
+    let hierarchy = Hierarchy::from_edges(edges.iter().map(|&(_, e)| e));
+    let mut up: HashMap<&'a str, Vec<(usize, &'a Edge)>> = HashMap::new();
+    for &(i, edge) in edges.iter() {
+        up.entry(edge.sub.as_str()).or_default().push((i, edge));
+    }
+    for edges in up.values_mut() {
+        edges.sort_by(|(_, a), (_, b)| (&a.obj, &a.pred).cmp(&(&b.obj, &b.pred)));
+    }
+
+    let mut ancestors: HashMap<&'a str, HashSet<&'a str>> = HashMap::new();
+    for node in hierarchy.nodes() {
+        let parents = hierarchy.parents(node);
+        for &parent in parents {
+            if !ancestors.contains_key(parent) {
+                let set = hierarchy.ancestors(parent, None).into_iter().collect();
+                ancestors.insert(parent, set);
+            }
+        }
+
+        let mut seen: HashMap<(&str, _), &'a Edge> = HashMap::new();
+        for &(i, edge) in up.get(node).map(Vec::as_slice).unwrap_or(&[]) {
+            // report duplicate edges against their first occurrence
+            let key = (edge.obj.as_str(), normalize_predicate(&edge.pred));
+            if let Some(&first) = seen.get(&key) {
+                redundant.insert(i, vec![first]);
+                continue;
+            }
+            seen.insert(key, edge);
+
+            // report edges implied by a path through another parent, unless
+            // that parent is in a cycle with the edge object or the node
+            let target = edge.obj.as_str();
+            let via = parents.iter().find(|&&p| {
+                p != target
+                    && ancestors[p].contains(target)
+                    && !ancestors[target].contains(p)
+                    && !ancestors[p].contains(node)
+            });
+            if let Some(&via) = via {
+                let first = hierarchy.edges_up(node).iter().find(|e| e.obj == via);
+                let rest = hierarchy.path_up(via, target);
+                if let (Some(&first), Some(rest)) = (first, rest) {
+                    let mut path = vec![first];
+                    path.extend(rest);
+                    redundant.insert(i, path);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::Depth;
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::error::Result;
+use crate::model::Graph;
+
+/// A measure of the semantic similarity between two terms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Measure {
+    /// The information content of the most informative common ancestor.
+    Resnik,
+    /// The Resnik similarity normalized by the information content of both terms.
+    Lin,
+    /// The inverse of the Jiang-Conrath distance, `1 / (1 + d)`.
+    JiangConrath,
+}
+
+/// Semantic similarity between the terms of a hierarchy.
+///
+/// The information content (IC) of each term is computed when the
+/// hierarchy is loaded, either intrinsically from the hierarchy structure
+/// or from the annotations of a corpus. The ancestors of each term are
+/// cached to compute the similarity measures efficiently.
+///
+/// The terms are the nodes of the [`Hierarchy`], including the `CLASS`
+/// nodes of the graph. Each term is considered to be one of its own
+/// ancestors.
+#[derive(Clone, Debug)]
+pub struct Similarity<'a> {
+    ancestors: HashMap<&'a str, HashSet<&'a str>>,
+    ic: HashMap<&'a str, f64>,
+}
+
+impl<'a> Similarity<'a> {
+    /// Use the intrinsic information content from Seco *et al.* (2004).
+    ///
+    /// The IC of a term with `n` descendants, in a hierarchy of `N` terms,
+    /// is `1 - ln(n + 1) / ln(N)`.
+    pub fn seco(graph: &'a Graph, preds: &PredicateSet) -> Self {
+        let hierarchy = Hierarchy::new(graph, preds);
+        let ancestors = ancestor_sets(&hierarchy);
+        let ic = seco_ic(&ancestors);
+        Self { ancestors, ic }
+    }
+
+    /// Use the intrinsic information content from Zhou *et al.* (2008).
+    ///
+    /// The IC of a term is `k` times its Seco IC, plus `1 - k` times
+    /// `ln(depth) / ln(max_depth)`. The depth of a term is its minimum
+    /// [`Depth`] plus one, since roots have a depth of 1 in Zhou *et al.*
+    ///
+    /// Fails with an `Error::Cycle` if the hierarchy is not acyclic.
+    pub fn zhou(graph: &'a Graph, preds: &PredicateSet, k: f64) -> Result<Self> {
+        let hierarchy = Hierarchy::new(graph, preds);
+        let depths = hierarchy.depths()?;
+        let ancestors = ancestor_sets(&hierarchy);
+
+        let depth = |d: &Depth| (d.min + 1) as f64;
+        let max_depth = depths.values().map(depth).fold(1.0, f64::max).ln();
+        let ic = seco_ic(&ancestors)
+            .into_iter()
+            .map(|(term, seco)| {
+                let deep = if max_depth > 0.0 {
+                    depth(&depths[term]).ln() / max_depth
+                } else {
+                    0.0
+                };
+                (term, k * seco + (1.0 - k) * deep)
+            })
+            .collect();
+
+        Ok(Self { ancestors, ic })
+    }
+
+    /// Use the information content computed from a corpus of annotations.
+    ///
+    /// The frequency of a term is the number of annotations to the term or
+    /// to any of its descendants, and its IC is `-ln(p)`, where `p` is its
+    /// frequency divided by the highest frequency. Terms without any
+    /// annotation have no IC.
+    pub fn corpus<I, S>(graph: &'a Graph, preds: &PredicateSet, counts: I) -> Self
+    where
+        I: IntoIterator<Item = (S, usize)>,
+        S: AsRef<str>,
+    {
+        let hierarchy = Hierarchy::new(graph, preds);
+        let ancestors = ancestor_sets(&hierarchy);
+
+        let mut frequencies = HashMap::<&str, usize>::new();
+        for (term, count) in counts {
+            if let Some(set) = ancestors.get(term.as_ref()) {
+                for &a in set.iter() {
+                    *frequencies.entry(a).or_default() += count;
+                }
+            }
+        }
+
+        let max = frequencies.values().copied().max().unwrap_or(0) as f64;
+        let ic = frequencies
+            .into_iter()
+            .filter(|&(_, f)| f > 0)
+            .map(|(term, f)| (term, -(f as f64 / max).ln()))
+            .collect();
+
+        Self { ancestors, ic }
+    }
+
+    /// Get the information content of a term, if any.
+    pub fn ic(&self, term: &str) -> Option<f64> {
+        self.ic.get(term).copied()
+    }
+
+    /// Get the ancestors of a term, including the term itself.
+    pub fn ancestors(&self, term: &str) -> Option<&HashSet<&'a str>> {
+        self.ancestors.get(term)
+    }
+
+    /// Get the most informative common ancestor of two terms, with its IC.
+    ///
+    /// Ties are broken by identifier.
+    pub fn mica(&self, a: &str, b: &str) -> Option<(&'a str, f64)> {
+        let (aa, ab) = (self.ancestors.get(a)?, self.ancestors.get(b)?);
+        let (small, large) = if aa.len() <= ab.len() {
+            (aa, ab)
+        } else {
+            (ab, aa)
+        };
+        small
+            .iter()
+            .filter(|c| large.contains(*c))
+            .filter_map(|&c| self.ic(c).map(|ic| (c, ic)))
+            .max_by(|(c1, x), (c2, y)| x.total_cmp(y).then(c2.cmp(c1)))
+    }
+
+    /// Compute the Resnik similarity between two terms.
+    ///
+    /// This is the IC of their most informative common ancestor, or `0` if
+    /// the terms have no common ancestor.
+    pub fn resnik(&self, a: &str, b: &str) -> f64 {
+        self.mica(a, b).map(|(_, ic)| ic).unwrap_or(0.0)
+    }
+
+    /// Compute the Lin similarity between two terms, between `0` and `1`.
+    pub fn lin(&self, a: &str, b: &str) -> f64 {
+        let total = self.ic(a).unwrap_or(0.0) + self.ic(b).unwrap_or(0.0);
+        if total > 0.0 {
+            2.0 * self.resnik(a, b) / total
+        } else if a == b && self.ancestors.contains_key(a) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Compute the Jiang-Conrath distance between two terms.
+    pub fn jiang_conrath_distance(&self, a: &str, b: &str) -> f64 {
+        let total = self.ic(a).unwrap_or(0.0) + self.ic(b).unwrap_or(0.0);
+        (total - 2.0 * self.resnik(a, b)).max(0.0)
+    }
+
+    /// Compute the Jiang-Conrath similarity between two terms, between `0` and `1`.
+    pub fn jiang_conrath(&self, a: &str, b: &str) -> f64 {
+        1.0 / (1.0 + self.jiang_conrath_distance(a, b))
+    }
+
+    /// Compute the similarity between two terms with the given measure.
+    pub fn similarity(&self, measure: Measure, a: &str, b: &str) -> f64 {
+        match measure {
+            Measure::Resnik => self.resnik(a, b),
+            Measure::Lin => self.lin(a, b),
+            Measure::JiangConrath => self.jiang_conrath(a, b),
+        }
+    }
+
+    /// Compute the SimGIC similarity between two sets of terms.
+    ///
+    /// This is the sum of the IC of the ancestors shared by both sets,
+    /// divided by the sum of the IC of the ancestors of either set.
+    pub fn sim_gic<A, B>(&self, a: A, b: B) -> f64
+    where
+        A: IntoIterator,
+        A::Item: AsRef<str>,
+        B: IntoIterator,
+        B::Item: AsRef<str>,
+    {
+        let aa = self.set_ancestors(a);
+        let ab = self.set_ancestors(b);
+        let union = aa.union(&ab).filter_map(|c| self.ic(c)).sum::<f64>();
+        let inter = aa.intersection(&ab).filter_map(|c| self.ic(c)).sum::<f64>();
+        if union > 0.0 {
+            inter / union
+        } else {
+            0.0
+        }
+    }
+
+    /// Compute the best-match average similarity between two sets of terms.
+    ///
+    /// Each term is matched with the most similar term of the other set
+    /// using the given measure, and the average similarities of the terms
+    /// of both sets are averaged.
+    pub fn best_match_average<A, B>(&self, measure: Measure, a: A, b: B) -> f64
+    where
+        A: IntoIterator,
+        A::Item: AsRef<str>,
+        B: IntoIterator,
+        B::Item: AsRef<str>,
+    {
+        let a = a.into_iter().collect::<Vec<_>>();
+        let b = b.into_iter().collect::<Vec<_>>();
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+
+        let best = |x: &A::Item| {
+            b.iter()
+                .map(|y| self.similarity(measure, x.as_ref(), y.as_ref()))
+                .fold(0.0, f64::max)
+        };
+        let ab = a.iter().map(best).sum::<f64>() / a.len() as f64;
+        let best = |y: &B::Item| {
+            a.iter()
+                .map(|x| self.similarity(measure, x.as_ref(), y.as_ref()))
+                .fold(0.0, f64::max)
+        };
+        let ba = b.iter().map(best).sum::<f64>() / b.len() as f64;
+        (ab + ba) / 2.0
+    }
+
+    fn set_ancestors<I>(&self, terms: I) -> HashSet<&'a str>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut set = HashSet::new();
+        for term in terms {
+            if let Some(ancestors) = self.ancestors.get(term.as_ref()) {
+                set.extend(ancestors.iter().copied());
+            }
+        }
+        set
+    }
+}
+
+/// Compute the reflexive ancestor sets of all the terms of a graph.
+fn ancestor_sets<'a>(hierarchy: &Hierarchy<'a>) -> HashMap<&'a str, HashSet<&'a str>> {
+    hierarchy
+        .nodes()
+        .map(|term| {
+            let mut set = hierarchy
+                .ancestors(term, None)
+                .into_iter()
+                .collect::<HashSet<_>>();
+            set.insert(term);
+            (term, set)
+        })
+        .collect()
+}
+
+/// Compute the intrinsic information content of each term from Seco *et al.*
+fn seco_ic<'a>(ancestors: &HashMap<&'a str, HashSet<&'a str>>) -> HashMap<&'a str, f64> {
+    let total = (ancestors.len() as f64).ln();
+    let mut descendants = HashMap::<&str, usize>::new();
+    for set in ancestors.values() {
+        for &a in set.iter() {
+            *descendants.entry(a).or_default() += 1;
+        }
+    }
+    ancestors
+        .keys()
+        .map(|&term| {
+            let hypo = (descendants[term] as f64).ln();
+            let seco = if total > 0.0 { 1.0 - hypo / total } else { 1.0 };
+            (term, seco)
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::error::Error;
+use crate::error::Result;
+use crate::model::Graph;
+
+/// The minimum and maximum depth of a node in a hierarchy.
+///
+/// The depth of a node is the number of edges on a path from a root to
+/// the node, so roots have a depth of `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Depth {
+    /// The length of the shortest path from a root.
+    pub min: usize,
+    /// The length of the longest path from a root.
+    pub max: usize,
+}
+
+impl<'a> Hierarchy<'a> {
+    /// Get the nodes without any parent, sorted by identifier.
+    pub fn roots(&self) -> Vec<&'a str> {
+        self.nodes()
+            .filter(|id| self.parents(id).is_empty())
+            .collect()
+    }
+
+    /// Get the nodes without any child, sorted by identifier.
+    pub fn leaves(&self) -> Vec<&'a str> {
+        self.nodes()
+            .filter(|id| self.children(id).is_empty())
+            .collect()
+    }
+
+    /// Sort the nodes so that each node comes after all of its parents.
+    ///
+    /// Ties are broken by identifier, so that the order is deterministic.
+    /// Fails with an `Error::Cycle` naming the nodes of a cycle if the
+    /// hierarchy is not acyclic.
+    pub fn topological_sort(&self) -> Result<Vec<&'a str>> {
+        let mut remaining = self
+            .nodes()
+            .map(|id| (id, self.parents(id).len()))
+            .collect::<HashMap<_, _>>();
+        let mut ready = remaining
+            .iter()
+            .filter(|(_, &n)| n == 0)
+            .map(|(&id, _)| Reverse(id))
+            .collect::<BinaryHeap<_>>();
+
+        let mut order = Vec::with_capacity(remaining.len());
+        while let Some(Reverse(id)) = ready.pop() {
+            remaining.remove(id);
+            order.push(id);
+            for &child in self.children(id) {
+                if let Some(n) = remaining.get_mut(child) {
+                    *n -= 1;
+                    if *n == 0 {
+                        ready.push(Reverse(child));
+                    }
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            Ok(order)
+        } else {
+            Err(Error::Cycle(self.find_cycle(&remaining)))
+        }
+    }
+
+    /// Compute the minimum and maximum depth of every node.
+    ///
+    /// Fails with an `Error::Cycle` if the hierarchy is not acyclic.
+    pub fn depths(&self) -> Result<HashMap<&'a str, Depth>> {
+        let mut depths = HashMap::new();
+        for id in self.topological_sort()? {
+            let parents = self.parents(id).iter().map(|p| depths[p]);
+            let depth = Depth {
+                min: parents.clone().map(|d: Depth| d.min + 1).min().unwrap_or(0),
+                max: parents.map(|d| d.max + 1).max().unwrap_or(0),
+            };
+            depths.insert(id, depth);
+        }
+        Ok(depths)
+    }
+
+    /// Find a cycle among nodes which all have at least one remaining parent.
+    fn find_cycle(&self, remaining: &HashMap<&'a str, usize>) -> Vec<String> {
+        let mut node = remaining.keys().copied().min().unwrap();
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        while seen.insert(node) {
+            path.push(node);
+            node = self
+                .parents(node)
+                .iter()
+                .copied()
+                .find(|p| remaining.contains_key(p))
+                .unwrap();
+        }
+        let start = path.iter().position(|&id| id == node).unwrap();
+        path[start..].iter().map(|id| id.to_string()).collect()
+    }
+}
+
+/// Get the root nodes of a graph over the given predicates.
+pub fn roots<'a>(graph: &'a Graph, preds: &PredicateSet) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).roots()
+}
+
+/// Get the leaf nodes of a graph over the given predicates.
+pub fn leaves<'a>(graph: &'a Graph, preds: &PredicateSet) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).leaves()
+}
+
+/// Compute the depth of the nodes of a graph over the given predicates.
+///
+/// See [`Hierarchy::depths`] for details.
+pub fn depths<'a>(graph: &'a Graph, preds: &PredicateSet) -> Result<HashMap<&'a str, Depth>> {
+    Hierarchy::new(graph, preds).depths()
+}
+
+/// Sort the nodes of a graph topologically over the given predicates.
+///
+/// See [`Hierarchy::topological_sort`] for details.
+pub fn topological_sort<'a>(graph: &'a Graph, preds: &PredicateSet) -> Result<Vec<&'a str>> {
+    Hierarchy::new(graph, preds).topological_sort()
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use super::Hierarchy;
+use super::PredicateSet;
+use crate::model::Edge;
+use crate::model::Graph;
+
+impl<'a> Hierarchy<'a> {
+    /// Get the direct parents of a node, sorted by identifier.
+    pub fn parents(&self, id: &str) -> &[&'a str] {
+        self.parents.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get the direct children of a node, sorted by identifier.
+    pub fn children(&self, id: &str) -> &[&'a str] {
+        self.children.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Get the ancestors of a node, up to an optional maximum depth.
+    ///
+    /// Ancestors are returned in breadth-first order, closest first, and
+    /// each ancestor is only returned once. The node itself is never part
+    /// of its ancestors, even if it belongs to a cycle.
+    pub fn ancestors(&self, id: &str, depth: Option<usize>) -> Vec<&'a str> {
+        self.breadth_first(id, depth, &self.parents)
+    }
+
+    /// Get the descendants of a node, up to an optional maximum depth.
+    ///
+    /// Descendants are returned in breadth-first order, closest first, and
+    /// each descendant is only returned once. The node itself is never part
+    /// of its descendants, even if it belongs to a cycle.
+    pub fn descendants(&self, id: &str, depth: Option<usize>) -> Vec<&'a str> {
+        self.breadth_first(id, depth, &self.children)
+    }
+
+    /// Get the shortest path of edges going up from a node to an ancestor.
+    ///
+    /// Returns `None` if `to` is not an ancestor of `from`, and an empty
+    /// path if both nodes are the same. Ties between paths of the same
+    /// length are broken by node identifier.
+    pub fn path_up(&self, from: &str, to: &str) -> Option<Vec<&'a Edge>> {
+        if from == to {
+            return Some(Vec::new());
+        }
+
+        let mut previous: HashMap<&str, &'a Edge> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(node) = queue.pop_front() {
+            for &edge in self.edges_up(node) {
+                let parent = edge.obj.as_str();
+                if parent == from || previous.contains_key(parent) {
+                    continue;
+                }
+                previous.insert(parent, edge);
+                if parent == to {
+                    let mut path = vec![edge];
+                    while path[path.len() - 1].sub != from {
+                        path.push(previous[path[path.len() - 1].sub.as_str()]);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(parent);
+            }
+        }
+        None
+    }
+
+    fn breadth_first(
+        &self,
+        id: &str,
+        depth: Option<usize>,
+        adjacency: &HashMap<&'a str, Vec<&'a str>>,
+    ) -> Vec<&'a str> {
+        let mut visited = HashSet::new();
+        visited.insert(id);
+
+        let mut result = Vec::new();
+        let mut layer = vec![id];
+        let mut level = 0;
+        while !layer.is_empty() && depth.map(|d| level < d).unwrap_or(true) {
+            let mut next = Vec::new();
+            for node in layer {
+                for &other in adjacency.get(node).map(Vec::as_slice).unwrap_or(&[]) {
+                    if visited.insert(other) {
+                        result.push(other);
+                        next.push(other);
+                    }
+                }
+            }
+            layer = next;
+            level += 1;
+        }
+        result
+    }
+}
+
+/// Get the direct parents of a node over the given predicates.
+pub fn parents<'a>(graph: &'a Graph, id: &str, preds: &PredicateSet) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).parents(id).to_vec()
+}
+
+/// Get the direct children of a node over the given predicates.
+pub fn children<'a>(graph: &'a Graph, id: &str, preds: &PredicateSet) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).children(id).to_vec()
+}
+
+/// Get the ancestors of a node over the given predicates.
+///
+/// See [`Hierarchy::ancestors`] for details. Build a [`Hierarchy`] once
+/// to query the ancestors of several nodes of the same graph.
+pub fn ancestors<'a>(
+    graph: &'a Graph,
+    id: &str,
+    preds: &PredicateSet,
+    depth: Option<usize>,
+) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).ancestors(id, depth)
+}
+
+/// Get the descendants of a node over the given predicates.
+///
+/// See [`Hierarchy::descendants`] for details. Build a [`Hierarchy`] once
+/// to query the descendants of several nodes of the same graph.
+pub fn descendants<'a>(
+    graph: &'a Graph,
+    id: &str,
+    preds: &PredicateSet,
+    depth: Option<usize>,
+) -> Vec<&'a str> {
+    Hierarchy::new(graph, preds).descendants(id, depth)
+}
@@ -94,6 +94,8 @@ pub mod property {
         pub const IS_CLASS_LEVEL: &str = concat!(oboInOwl!(), "is_class_level");
         pub const IS_METADATA_TAG: &str = concat!(oboInOwl!(), "is_metadata_tag");
         pub const IS_ANTI_SYMMETRIC: &str = concat!(oboInOwl!(), "is_anti_symmetric");
+        pub const IS_TRANSITIVE: &str = concat!(oboInOwl!(), "is_transitive");
+        pub const IS_INFERRED: &str = concat!(oboInOwl!(), "is_inferred");
         pub const DISJOINT_OVER: &str = concat!(oboInOwl!(), "disjoint_over");
     }
 
@@ -102,10 +104,16 @@ pub mod property {
         pub const DEPRECATED: &str = concat!(owl!(), "deprecated");
     }
 
+    /// RDF vocabulary properties.
+    pub mod rdf {
+        pub const TYPE: &str = concat!(rdf!(), "type");
+    }
+
     /// RDF Schema annotation properties.
     pub mod rdfs {
         pub const LABEL: &str = concat!(rdfs!(), "label");
         pub const COMMENT: &str = concat!(rdfs!(), "comment");
+        pub const SUB_CLASS_OF: &str = concat!(rdfs!(), "subClassOf");
         pub const SUB_PROPERTY_OF: &str = concat!(rdfs!(), "subPropertyOf");
     }
 
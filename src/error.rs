@@ -9,16 +9,71 @@ pub enum Error {
     JsonError(#[from] serde_json::Error),
     #[error(transparent)]
     IOError(#[from] std::io::Error),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     #[error(transparent)]
     OboSyntaxError(#[from] fastobo::error::SyntaxError),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    #[error(transparent)]
+    OboError(#[from] fastobo::error::Error),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     #[error("{0}: {1:?}")]
     InvalidBoolean(#[source] std::str::ParseBoolError, String),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     #[error("invalid synonym type: {0:?}")]
     InvalidSynonymType(String),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     #[error("invalid term clause: {0:?}")]
     InvalidTermClause(String),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     #[error("invalid instance clause: {0:?}")]
     InvalidInstanceClause(String),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    #[error("edge refers to an undeclared node: {0:?}")]
+    DanglingEdge(String),
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    #[error("edge subject {edge:?} does not match node {node:?}")]
+    EdgeSubjectMismatch { node: String, edge: String },
+    #[error("hierarchy contains a cycle: {0:?}")]
+    Cycle(Vec<String>),
+    #[error("{0} compressed files require the `{0}` feature")]
+    UnsupportedCompression(&'static str),
+    #[error("invalid JSON at `{path}`: {error}")]
+    JsonPathError {
+        path: String,
+        #[source]
+        error: serde_json::Error,
+    },
+    #[error("invalid YAML at `{path}`: {error}")]
+    YamlPathError {
+        path: String,
+        #[source]
+        error: serde_yaml::Error,
+    },
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    #[error("invalid identifier in {context}: {value:?}")]
+    InvalidIdentifier {
+        context: String,
+        value: String,
+        #[source]
+        error: fastobo::error::SyntaxError,
+    },
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    #[error("failed to convert {id:?}: {error}")]
+    EntityError {
+        id: String,
+        #[source]
+        error: Box<Error>,
+    },
 }
 
 /// The result type for this crate.
@@ -26,12 +81,68 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 impl Error {
     /// Create a new `Error::InvalidInstanceClause` error variant.
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     pub fn invalid_instance_clause<S: Into<String>>(clause: S) -> Self {
         Error::InvalidInstanceClause(clause.into())
     }
 
     /// Create a new `Error::InvalidTermClause` error variant.
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
     pub fn invalid_term_clause<S: Into<String>>(clause: S) -> Self {
         Error::InvalidTermClause(clause.into())
     }
+
+    /// Create a new `Error::InvalidIdentifier` error variant.
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    pub fn invalid_identifier<C, V>(
+        context: C,
+        value: V,
+        error: fastobo::error::SyntaxError,
+    ) -> Self
+    where
+        C: Into<String>,
+        V: Into<String>,
+    {
+        Error::InvalidIdentifier {
+            context: context.into(),
+            value: value.into(),
+            error,
+        }
+    }
+
+    /// Wrap the error in an `Error::EntityError` for the given entity.
+    ///
+    /// Errors already wrapped for an entity are returned unchanged.
+    #[cfg(feature = "obo")]
+    #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+    pub fn in_entity<S: Into<String>>(self, id: S) -> Self {
+        match self {
+            Error::EntityError { .. } => self,
+            error => Error::EntityError {
+                id: id.into(),
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+    fn from(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Error::JsonPathError {
+            path: error.path().to_string(),
+            error: error.into_inner(),
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_yaml::Error>> for Error {
+    fn from(error: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        Error::YamlPathError {
+            path: error.path().to_string(),
+            error: error.into_inner(),
+        }
+    }
 }
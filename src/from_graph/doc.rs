@@ -1,111 +1,185 @@
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::collections::HashSet;
 
 use fastobo::ast::ClassIdent;
 use fastobo::ast::EntityFrame;
 use fastobo::ast::HeaderClause;
 use fastobo::ast::HeaderFrame;
 use fastobo::ast::Ident;
-use fastobo::ast::InstanceClause;
+use fastobo::ast::InstanceFrame;
+use fastobo::ast::InstanceIdent;
 use fastobo::ast::Line;
 use fastobo::ast::OboDoc;
 use fastobo::ast::RelationIdent;
 use fastobo::ast::TermClause;
+use fastobo::ast::TermFrame;
 use fastobo::ast::TypedefClause;
+use fastobo::ast::TypedefFrame;
 
 use fastobo::semantics::Identified;
 use fastobo::semantics::Orderable;
 use fastobo::visit::IdCompactor;
 use fastobo::visit::VisitMut;
 
+use super::edge::push_edge;
+use super::infer::infer_types;
+use super::infer::is_rdf_type;
+use super::parse_ident;
+use super::Context;
+use super::DanglingEdges;
+use super::Diagnostic;
 use super::FromGraph;
+use super::FromGraphCtx;
 use crate::error::Error;
 use crate::error::Result;
 use crate::model::Graph;
+use crate::model::NodeType;
+
+/// Create a stub frame for an identifier referenced but not declared.
+fn stub(id: &Ident, ty: NodeType) -> EntityFrame {
+    match ty {
+        NodeType::Class => {
+            let frame = TermFrame::new(Line::from(ClassIdent::from(id.clone())));
+            EntityFrame::Term(Box::new(frame))
+        }
+        NodeType::Property => {
+            let frame = TypedefFrame::new(Line::from(RelationIdent::from(id.clone())));
+            EntityFrame::Typedef(Box::new(frame))
+        }
+        NodeType::Individual => {
+            let frame = InstanceFrame::new(Line::from(InstanceIdent::from(id.clone())));
+            EntityFrame::Instance(Box::new(frame))
+        }
+    }
+}
+
+/// Check whether an edge predicate is converted into a dedicated clause.
+fn is_builtin(pred: &str) -> bool {
+    matches!(pred, "is_a" | "subClassOf" | "subPropertyOf" | "inverseOf") || is_rdf_type(pred)
+}
 
 impl FromGraph<Graph> for OboDoc {
+    #[inline]
     fn from_graph(graph: Graph) -> Result<Self> {
+        Self::from_graph_ctx(graph, &mut Context::default())
+    }
+}
+
+impl FromGraphCtx<Graph> for OboDoc {
+    fn from_graph_ctx(mut graph: Graph, ctx: &mut Context) -> Result<Self> {
+        // Give a type to untyped nodes if configured to do so.
+        infer_types(&mut graph, ctx);
+
         let mut entities = HashMap::new();
+        let mut declared = HashSet::new();
         for node in graph.nodes.into_iter() {
-            if let Some(frame) = <Option<EntityFrame>>::from_graph(node)? {
-                entities.insert(frame.as_id().clone(), frame);
+            if node.ty.is_none() {
+                ctx.diagnostics
+                    .push(Diagnostic::UntypedNode { id: node.id });
+            } else {
+                let id = node.id.clone();
+                let frame = match <Option<EntityFrame>>::from_graph(node) {
+                    Ok(frame) => frame,
+                    Err(e) => return Err(e.in_entity(id)),
+                };
+                if let Some(frame) = frame {
+                    declared.insert(id);
+                    entities.insert(frame.as_id().clone(), frame);
+                }
             }
         }
 
         for edge in graph.edges.iter() {
-            let id_sub = Ident::from_str(&edge.sub)?;
-            let id_pred = RelationIdent::from_str(&edge.pred)?;
-            let id_obj = Ident::from_str(&edge.obj)?;
-            if &edge.pred == "is_a" || &edge.pred == "subPropertyOf" || &edge.pred == "subClassOf" {
-                match entities.get_mut(&id_sub) {
-                    Some(EntityFrame::Term(ref mut frame)) => {
-                        let c = TermClause::IsA(Box::new(From::from(id_obj)));
-                        frame.push(Line::from(c));
-                    }
-                    Some(EntityFrame::Typedef(ref mut frame)) => {
-                        let c = TypedefClause::IsA(Box::new(From::from(id_obj)));
-                        frame.push(Line::from(c));
-                    }
-                    Some(EntityFrame::Instance(_)) => {
-                        return Err(Error::invalid_instance_clause("is_a"));
-                    }
-                    None => (),
-                }
-            } else if &edge.pred == "inverseOf" {
-                match entities.get_mut(&id_sub) {
-                    Some(EntityFrame::Typedef(ref mut frame)) => {
-                        let c = TypedefClause::InverseOf(Box::new(From::from(id_obj)));
-                        frame.push(Line::from(c));
+            let id_sub = parse_ident::<Ident>("edge subject", &edge.sub)?;
+            let id_obj = parse_ident::<Ident>("edge object", &edge.obj)?;
+
+            // Handle edges referring to nodes that were not converted.
+            let has_sub = entities.contains_key(&id_sub);
+            let has_obj = entities.contains_key(&id_obj);
+            if !has_sub || !has_obj {
+                match ctx.config.dangling_edges {
+                    DanglingEdges::Keep if has_sub => (),
+                    DanglingEdges::Keep | DanglingEdges::Drop => {
+                        ctx.diagnostics.push(Diagnostic::DanglingEdge {
+                            sub: edge.sub.clone(),
+                            pred: edge.pred.clone(),
+                            obj: edge.obj.clone(),
+                        });
+                        continue;
                     }
-                    Some(EntityFrame::Term(ref mut frame)) => {
-                        return Err(Error::invalid_term_clause("inverse_of"));
+                    DanglingEdges::Error => {
+                        let id = if has_sub { &edge.obj } else { &edge.sub };
+                        return Err(Error::DanglingEdge(id.clone()));
                     }
-                    Some(EntityFrame::Instance(_)) => {
-                        return Err(Error::invalid_instance_clause("inverse_of"));
+                    DanglingEdges::Stub => {
+                        let pred = edge.pred.as_str();
+                        let relation = matches!(pred, "subPropertyOf" | "inverseOf");
+                        let sub_typedef =
+                            matches!(entities.get(&id_sub), Some(EntityFrame::Typedef(_)));
+                        let obj_typedef =
+                            matches!(entities.get(&id_obj), Some(EntityFrame::Typedef(_)));
+                        if !has_sub {
+                            let ty = if relation || (pred == "is_a" && obj_typedef) {
+                                NodeType::Property
+                            } else if is_rdf_type(pred) {
+                                NodeType::Individual
+                            } else {
+                                NodeType::Class
+                            };
+                            entities.insert(id_sub.clone(), stub(&id_sub, ty));
+                            ctx.diagnostics.push(Diagnostic::StubFrame {
+                                id: edge.sub.clone(),
+                            });
+                        }
+                        if !has_obj && id_obj != id_sub {
+                            let ty = if relation || (pred == "is_a" && sub_typedef) {
+                                NodeType::Property
+                            } else {
+                                NodeType::Class
+                            };
+                            entities.insert(id_obj.clone(), stub(&id_obj, ty));
+                            ctx.diagnostics.push(Diagnostic::StubFrame {
+                                id: edge.obj.clone(),
+                            });
+                        }
                     }
-                    None => (),
                 }
-            } else {
-                match entities.get_mut(&id_sub) {
-                    Some(EntityFrame::Term(ref mut frame)) => {
-                        let c = TermClause::Relationship(
-                            Box::new(id_pred),
-                            Box::new(From::from(id_obj)),
-                        );
-                        frame.push(Line::from(c));
-                    }
-                    Some(EntityFrame::Typedef(ref mut frame)) => {
-                        let c = TypedefClause::Relationship(
-                            Box::new(id_pred),
-                            Box::new(From::from(id_obj)),
-                        );
-                        frame.push(Line::from(c));
-                    }
-                    Some(EntityFrame::Instance(ref mut frame)) => {
-                        let c = InstanceClause::Relationship(
-                            Box::new(id_pred),
-                            Box::new(From::from(id_obj)),
-                        );
-                        frame.push(Line::from(c));
-                    }
-                    None => (),
+            }
+
+            // Stub the typedef of the relationship if needed.
+            if matches!(ctx.config.dangling_edges, DanglingEdges::Stub)
+                && !is_builtin(&edge.pred)
+                && !declared.contains(&edge.pred)
+            {
+                let id_pred = parse_ident::<Ident>("edge predicate", &edge.pred)?;
+                if !entities.contains_key(&id_pred) {
+                    entities.insert(id_pred.clone(), stub(&id_pred, NodeType::Property));
+                    ctx.diagnostics.push(Diagnostic::StubFrame {
+                        id: edge.pred.clone(),
+                    });
                 }
             }
+
+            if let Some(frame) = entities.get_mut(&id_sub) {
+                push_edge(frame, &edge.pred, id_obj)?;
+            }
         }
 
         for eq in graph.equivalent_nodes_sets.iter() {
             for node in eq.node_ids.iter() {
-                let node_id = Ident::from_str(&node)?;
+                let node_id = parse_ident::<Ident>("equivalent nodes set", node)?;
                 match entities.get_mut(&node_id) {
                     Some(EntityFrame::Term(ref mut frame)) => {
                         for node in eq.node_ids.iter().filter(|&n| n != node) {
-                            let id = ClassIdent::from_str(&node).map(Box::new)?;
+                            let id = parse_ident::<ClassIdent>("equivalent nodes set", node)
+                                .map(Box::new)?;
                             frame.push(Line::from(TermClause::EquivalentTo(id)));
                         }
                     }
                     Some(EntityFrame::Typedef(ref mut frame)) => {
                         for node in eq.node_ids.iter().filter(|&n| n != node) {
-                            let id = RelationIdent::from_str(&node).map(Box::new)?;
+                            let id = parse_ident::<RelationIdent>("equivalent nodes set", node)
+                                .map(Box::new)?;
                             frame.push(Line::from(TypedefClause::EquivalentTo(id)));
                         }
                     }
@@ -118,14 +192,14 @@ impl FromGraph<Graph> for OboDoc {
         }
 
         for dr in graph.domain_range_axioms.iter() {
-            let id = Ident::from_str(&dr.predicate_id)?;
+            let id = parse_ident::<Ident>("domain range axiom", &dr.predicate_id)?;
             if let Some(EntityFrame::Typedef(ref mut frame)) = entities.get_mut(&id) {
                 for domain in dr.domain_class_ids.iter() {
-                    let domain_id = ClassIdent::from_str(&domain).map(Box::new)?;
+                    let domain_id = parse_ident::<ClassIdent>("domain", domain).map(Box::new)?;
                     frame.push(Line::from(TypedefClause::Domain(domain_id)));
                 }
                 for range in dr.range_class_ids.iter() {
-                    let range_id = ClassIdent::from_str(&range).map(Box::new)?;
+                    let range_id = parse_ident::<ClassIdent>("range", range).map(Box::new)?;
                     frame.push(Line::from(TypedefClause::Range(range_id)));
                 }
                 // TODO: allValuesFromEdges
@@ -139,7 +213,7 @@ impl FromGraph<Graph> for OboDoc {
             if let Some(ont) = graph.id.strip_prefix(crate::constants::uri::OBO) {
                 let id = ont.trim_end_matches(".obo").trim_end_matches(".owl");
                 header.push(HeaderClause::Ontology(Box::new(id.into())));
-                if let Some(ref v) = version_iri
+                if let Some(v) = version_iri
                     .as_ref()
                     .and_then(|url| url.strip_prefix(crate::constants::uri::OBO))
                 {
@@ -158,8 +232,7 @@ impl FromGraph<Graph> for OboDoc {
         };
 
         // collect entities and merge them into a document
-        let mut doc = OboDoc::with_header(header)
-            .and_entities(entities.into_iter().map(|(_, v)| v).collect());
+        let mut doc = OboDoc::with_header(header).and_entities(entities.into_values().collect());
         doc.sort();
         IdCompactor::new().visit_doc(&mut doc);
 
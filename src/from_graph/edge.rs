@@ -0,0 +1,77 @@
+use fastobo::ast::EntityFrame;
+use fastobo::ast::Ident;
+use fastobo::ast::InstanceClause;
+use fastobo::ast::Line;
+use fastobo::ast::RelationIdent;
+use fastobo::ast::TermClause;
+use fastobo::ast::TypedefClause;
+
+use super::infer::is_rdf_type;
+use super::parse_ident;
+use crate::error::Error;
+use crate::error::Result;
+
+/// Add the clause for an edge with the given predicate and object to a frame.
+pub fn push_edge(frame: &mut EntityFrame, pred: &str, id_obj: Ident) -> Result<()> {
+    if pred == "is_a" || pred == "subPropertyOf" || pred == "subClassOf" {
+        match frame {
+            EntityFrame::Term(ref mut frame) => {
+                let c = TermClause::IsA(Box::new(From::from(id_obj)));
+                frame.push(Line::from(c));
+            }
+            EntityFrame::Typedef(ref mut frame) => {
+                let c = TypedefClause::IsA(Box::new(From::from(id_obj)));
+                frame.push(Line::from(c));
+            }
+            EntityFrame::Instance(_) => {
+                return Err(Error::invalid_instance_clause("is_a"));
+            }
+        }
+    } else if pred == "inverseOf" {
+        match frame {
+            EntityFrame::Typedef(ref mut frame) => {
+                let c = TypedefClause::InverseOf(Box::new(From::from(id_obj)));
+                frame.push(Line::from(c));
+            }
+            EntityFrame::Term(_) => {
+                return Err(Error::invalid_term_clause("inverse_of"));
+            }
+            EntityFrame::Instance(_) => {
+                return Err(Error::invalid_instance_clause("inverse_of"));
+            }
+        }
+    } else if is_rdf_type(pred) {
+        match frame {
+            EntityFrame::Instance(ref mut frame) => {
+                let c = InstanceClause::InstanceOf(Box::new(From::from(id_obj)));
+                frame.push(Line::from(c));
+            }
+            // `instance_of` is only valid in instance frames, so keep the
+            // edge as a plain relationship in other frames.
+            _ => push_relationship(frame, pred, id_obj)?,
+        }
+    } else {
+        push_relationship(frame, pred, id_obj)?;
+    }
+    Ok(())
+}
+
+/// Add a `relationship` clause with the given predicate and object to a frame.
+fn push_relationship(frame: &mut EntityFrame, pred: &str, id_obj: Ident) -> Result<()> {
+    let id_pred = parse_ident::<RelationIdent>("edge predicate", pred)?;
+    match frame {
+        EntityFrame::Term(ref mut frame) => {
+            let c = TermClause::Relationship(Box::new(id_pred), Box::new(From::from(id_obj)));
+            frame.push(Line::from(c));
+        }
+        EntityFrame::Typedef(ref mut frame) => {
+            let c = TypedefClause::Relationship(Box::new(id_pred), Box::new(From::from(id_obj)));
+            frame.push(Line::from(c));
+        }
+        EntityFrame::Instance(ref mut frame) => {
+            let c = InstanceClause::Relationship(Box::new(id_pred), Box::new(id_obj));
+            frame.push(Line::from(c));
+        }
+    }
+    Ok(())
+}
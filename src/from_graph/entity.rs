@@ -10,35 +10,42 @@ use fastobo::ast::InstanceClause;
 use fastobo::ast::InstanceFrame;
 use fastobo::ast::InstanceIdent;
 use fastobo::ast::Line;
-use fastobo::ast::LiteralPropertyValue;
-use fastobo::ast::PrefixedIdent;
 use fastobo::ast::PropertyValue;
+use fastobo::ast::Qualifier;
+use fastobo::ast::QualifierList;
 use fastobo::ast::QuotedString;
 use fastobo::ast::RelationIdent;
-use fastobo::ast::ResourcePropertyValue;
 use fastobo::ast::SubsetIdent;
 use fastobo::ast::Synonym;
 use fastobo::ast::TermClause;
 use fastobo::ast::TermFrame;
 use fastobo::ast::TypedefClause;
 use fastobo::ast::TypedefFrame;
+use fastobo::ast::UnprefixedIdent;
 use fastobo::ast::UnquotedString;
 use fastobo::ast::Xref;
 use fastobo::ast::XrefList;
+use fastobo::visit::IdCompactor;
+use fastobo::visit::VisitMut;
 
 use crate::constants::property::dc;
 use crate::constants::property::iao;
 use crate::constants::property::obo_in_owl;
 use crate::constants::property::rdfs;
+use crate::constants::uri;
 use crate::error::Error;
 use crate::error::Result;
 
 use crate::model::BasicPropertyValue;
 use crate::model::DefinitionPropertyValue;
+use crate::model::Edge;
 use crate::model::Meta;
 use crate::model::Node;
 use crate::model::NodeType;
+use crate::model::PropertyType;
 
+use super::edge::push_edge;
+use super::parse_ident;
 use super::FromGraph;
 
 // ---------------------------------------------------------------------------
@@ -57,8 +64,8 @@ macro_rules! impl_frame_inner {
                 frame.push(Line::from(name));
             }
             if let Some(meta) = $node.meta {
-                let clauses: Vec<Clause> = FromGraph::from_graph(*meta)?;
-                frame.extend(clauses.into_iter().map(Line::from));
+                let lines: Vec<Line<Clause>> = FromGraph::from_graph(*meta)?;
+                frame.extend(lines);
             }
             Ok(Some(EntityFrame::Variant(Box::new(frame))))
         }
@@ -67,35 +74,44 @@ macro_rules! impl_frame_inner {
 
 impl FromGraph<Node> for Option<EntityFrame> {
     fn from_graph(node: Node) -> Result<Self> {
-        let id = Ident::from_str(&node.id)?;
+        let id = parse_ident::<Ident>("node id", &node.id)?;
         match node.ty {
             None => Ok(None),
             Some(NodeType::Class) => impl_frame_inner!(node, id, ClassIdent, Term),
             Some(NodeType::Individual) => impl_frame_inner!(node, id, InstanceIdent, Instance),
             Some(NodeType::Property) => {
+                let property_type = node.property_type;
                 // replace ID with `oboInOwl:shorthand` if possible.
                 match impl_frame_inner!(node, id, RelationIdent, Typedef) {
                     Ok(Some(EntityFrame::Typedef(mut frame))) => {
-                        if let Some((idx, _)) = frame.iter().enumerate().find(|(_, c)| {
-                            if let TypedefClause::PropertyValue(pv) = c.as_inner() {
-                                if let PropertyValue::Literal(lpv) = pv.as_ref() {
-                                    match lpv.property().as_ref() {
-                                        Ident::Url(url) => url.as_str() == obo_in_owl::SHORTHAND,
-                                        _ => false,
-                                    }
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            }
+                        // annotation properties are metadata tags in OBO, unless
+                        // they are declared as such or as class-level relations.
+                        if property_type == Some(PropertyType::Annotation)
+                            && !frame.iter().any(|c| {
+                                matches!(
+                                    c.as_inner(),
+                                    TypedefClause::IsMetadataTag(_)
+                                        | TypedefClause::IsClassLevel(true)
+                                )
+                            })
+                        {
+                            frame.push(Line::from(TypedefClause::IsMetadataTag(true)));
+                        }
+                        if let Some(idx) = frame.iter().position(|c| match c.as_inner() {
+                            TypedefClause::PropertyValue(pv) => match pv.property().as_ref() {
+                                Ident::Url(url) => url.as_str() == obo_in_owl::SHORTHAND,
+                                _ => false,
+                            },
+                            _ => false,
                         }) {
                             let new_id = match frame.remove(idx).into_inner() {
                                 TypedefClause::PropertyValue(pv) => match *pv {
                                     PropertyValue::Resource(rpv) => {
                                         RelationIdent::from(rpv.target().clone())
                                     }
-                                    _ => unreachable!(),
+                                    PropertyValue::Literal(lpv) => {
+                                        parse_ident("shorthand", lpv.literal().as_str())?
+                                    }
                                 },
                                 _ => unreachable!(),
                             };
@@ -110,36 +126,68 @@ impl FromGraph<Node> for Option<EntityFrame> {
     }
 }
 
+impl FromGraph<(Node, Vec<Edge>)> for Option<EntityFrame> {
+    fn from_graph((node, edges): (Node, Vec<Edge>)) -> Result<Self> {
+        let id = node.id.clone();
+        let mut frame = match <Option<EntityFrame>>::from_graph(node)? {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+        for edge in edges {
+            if edge.sub != id {
+                return Err(Error::EdgeSubjectMismatch {
+                    node: id,
+                    edge: edge.sub,
+                });
+            }
+            push_edge(
+                &mut frame,
+                &edge.pred,
+                parse_ident("edge object", &edge.obj)?,
+            )?;
+        }
+        IdCompactor::new().visit_entity_frame(&mut frame);
+        Ok(Some(frame))
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 macro_rules! impl_meta {
     ($clause:ident) => {
-        impl FromGraph<Meta> for Vec<$clause> {
+        impl FromGraph<Meta> for Vec<Line<$clause>> {
             fn from_graph(meta: Meta) -> Result<Self> {
-                let mut clauses = Vec::new();
+                let mut lines = Vec::new();
                 if let Some(desc) = meta.definition {
-                    clauses.push($clause::from_graph(*desc)?)
+                    lines.push(Line::<$clause>::from_graph(*desc)?)
                 }
                 for comment in meta.comments {
-                    clauses.push($clause::Comment(Box::new(UnquotedString::new(comment))));
+                    lines.push($clause::Comment(Box::new(UnquotedString::new(comment))).into());
                 }
                 for subset in meta.subsets {
-                    let id = SubsetIdent::from_str(&subset)?;
-                    clauses.push($clause::Subset(Box::new(id)));
+                    let id = parse_ident::<SubsetIdent>("subset", &subset)?;
+                    lines.push($clause::Subset(Box::new(id)).into());
                 }
                 for xref in meta.xrefs {
-                    clauses.push($clause::Xref(Box::new(Xref::from_graph(xref)?)));
+                    lines.push($clause::Xref(Box::new(Xref::from_graph(xref)?)).into());
                 }
                 for synonym in meta.synonyms {
-                    clauses.push($clause::Synonym(Box::new(Synonym::from_graph(synonym)?)));
+                    lines.push($clause::Synonym(Box::new(Synonym::from_graph(synonym)?)).into());
                 }
                 for pv in meta.basic_property_values {
-                    clauses.push($clause::from_graph(pv)?);
+                    lines.push($clause::from_graph(pv)?.into());
                 }
                 if meta.deprecated {
-                    clauses.push($clause::IsObsolete(true));
+                    lines.push($clause::IsObsolete(true).into());
                 }
-                Ok(clauses)
+                Ok(lines)
+            }
+        }
+
+        impl FromGraph<Meta> for Vec<$clause> {
+            fn from_graph(meta: Meta) -> Result<Self> {
+                let lines: Vec<Line<$clause>> = FromGraph::from_graph(meta)?;
+                Ok(lines.into_iter().map(Line::into_inner).collect())
             }
         }
     };
@@ -153,15 +201,64 @@ impl_meta!(InstanceClause);
 
 macro_rules! impl_definition_pv {
     ($clause:ident) => {
-        impl FromGraph<DefinitionPropertyValue> for $clause {
+        impl FromGraph<DefinitionPropertyValue> for Line<$clause> {
             fn from_graph(pv: DefinitionPropertyValue) -> Result<Self> {
+                let meta = pv.meta.map(|m| *m).unwrap_or_default();
+
+                // a definition with an unknown predicate is a plain annotation
+                match pv.pred.as_deref() {
+                    None | Some(iao::DEFINITION) | Some(obo_in_owl::HAS_DEFINITION) => (),
+                    Some(pred) => {
+                        let bpv = BasicPropertyValue::new(pred.to_string(), pv.val);
+                        return $clause::from_graph(bpv).map(Line::from);
+                    }
+                }
+
+                // recover xref descriptions from the definition metadata
                 let value = QuotedString::new(pv.val);
                 let xrefs = pv
                     .xrefs
                     .into_iter()
-                    .map(|id: String| Ident::from_str(&id).map(Xref::new).map_err(Error::from))
+                    .map(|id: String| {
+                        let desc = meta
+                            .xrefs
+                            .iter()
+                            .find(|x| x.val == id)
+                            .and_then(|x| x.label.as_ref());
+                        let ident = parse_ident::<Ident>("definition xref", &id)?;
+                        Ok(match desc {
+                            Some(d) => Xref::with_desc(ident, QuotedString::new(d.clone())),
+                            None => Xref::new(ident),
+                        })
+                    })
                     .collect::<Result<XrefList>>()?;
-                Ok($clause::Def(Box::new(Definition::with_xrefs(value, xrefs))))
+
+                // recover qualifiers from the definition annotations
+                let qualifiers = meta
+                    .basic_property_values
+                    .into_iter()
+                    .map(|bpv| {
+                        let key = match bpv.pred.strip_prefix(uri::OBO_IN_OWL) {
+                            Some(local) => RelationIdent::from(UnprefixedIdent::new(local)),
+                            None => parse_ident::<RelationIdent>("qualifier", &bpv.pred)?,
+                        };
+                        Ok(Qualifier::new(key, QuotedString::new(bpv.val)))
+                    })
+                    .collect::<Result<Vec<Qualifier>>>()?;
+
+                let def = Definition::with_xrefs(value, xrefs);
+                let line = Line::from($clause::Def(Box::new(def)));
+                if qualifiers.is_empty() {
+                    Ok(line)
+                } else {
+                    Ok(line.and_qualifiers(QualifierList::new(qualifiers)))
+                }
+            }
+        }
+
+        impl FromGraph<DefinitionPropertyValue> for $clause {
+            fn from_graph(pv: DefinitionPropertyValue) -> Result<Self> {
+                Line::<$clause>::from_graph(pv).map(Line::into_inner)
             }
         }
     };
@@ -180,11 +277,11 @@ macro_rules! impl_basic_pv_common {
                 Ok($clause::Comment(Box::new(UnquotedString::new($pv.val))))
             },
             obo_in_owl::HAS_ALTERNATIVE_ID => {
-                let id = Ident::from_str(&$pv.val)?;
+                let id = parse_ident::<Ident>("alternative id", &$pv.val)?;
                 Ok($clause::AltId(Box::new(id.into())))
             },
             obo_in_owl::HAS_OBO_NAMESPACE => {
-                let id = Ident::from_str(&$pv.val)?;
+                let id = parse_ident::<Ident>("namespace", &$pv.val)?;
                 Ok($clause::Namespace(Box::new(id.into())))
             },
             obo_in_owl::CREATED_BY | dc::CREATOR => {
@@ -195,20 +292,12 @@ macro_rules! impl_basic_pv_common {
                 Ok($clause::CreationDate(Box::new(date)))
             }
             iao::REPLACED_BY => {
-                let id = Ident::from_str(&$pv.val)?;
+                let id = parse_ident::<Ident>("replaced by", &$pv.val)?;
                 Ok($clause::ReplacedBy(Box::new(id.into())))
             }
             $( $l => $r ),*
-            other => {
-                let rel = RelationIdent::from_str(&other)?;
-                let pv = match Ident::from_str(&$pv.val) {
-                    Ok(id) => PropertyValue::from(ResourcePropertyValue::new(rel, id)),
-                    Err(_) => PropertyValue::from(LiteralPropertyValue::new(
-                        rel,
-                        QuotedString::new($pv.val),
-                        Ident::from(PrefixedIdent::new("xsd", "string"))
-                    ))
-                };
+            _ => {
+                let pv = PropertyValue::from_graph($pv)?;
                 Ok($clause::PropertyValue(Box::new(pv)))
             },
         }
@@ -238,6 +327,12 @@ impl FromGraph<BasicPropertyValue> for TypedefClause {
                     Err(e) => Err(Error::InvalidBoolean(e, pv.val.to_string())),
                 }
             },
+            obo_in_owl::IS_TRANSITIVE => {
+                match bool::from_str(&pv.val) {
+                    Ok(b) => Ok(TypedefClause::IsTransitive(b)),
+                    Err(e) => Err(Error::InvalidBoolean(e, pv.val.to_string())),
+                }
+            },
             obo_in_owl::IS_CLASS_LEVEL => {
                 match bool::from_str(&pv.val) {
                     Ok(b) => Ok(TypedefClause::IsClassLevel(b)),
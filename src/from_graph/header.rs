@@ -2,17 +2,12 @@ use std::str::FromStr;
 
 use fastobo::ast::HeaderClause;
 use fastobo::ast::HeaderFrame;
-use fastobo::ast::Ident;
-use fastobo::ast::LiteralPropertyValue;
 use fastobo::ast::NaiveDateTime;
 use fastobo::ast::NamespaceIdent;
-use fastobo::ast::PrefixedIdent;
 use fastobo::ast::PropertyValue;
-use fastobo::ast::QuotedString;
-use fastobo::ast::RelationIdent;
-use fastobo::ast::ResourcePropertyValue;
 use fastobo::ast::UnquotedString;
 
+use super::parse_ident;
 use super::FromGraph;
 use crate::constants::property::dc;
 use crate::constants::property::obo_in_owl;
@@ -54,7 +49,7 @@ impl FromGraph<BasicPropertyValue> for HeaderClause {
                 Ok(HeaderClause::Date(Box::new(dt)))
             }
             obo_in_owl::HAS_DEFAULT_NAMESPACE => {
-                let ns_id = NamespaceIdent::from_str(&pv.val)?;
+                let ns_id = parse_ident::<NamespaceIdent>("default namespace", &pv.val)?;
                 Ok(HeaderClause::DefaultNamespace(Box::new(ns_id)))
             }
             obo_in_owl::HAS_OBO_FORMAT_VERSION => Ok(HeaderClause::FormatVersion(Box::new(
@@ -67,16 +62,8 @@ impl FromGraph<BasicPropertyValue> for HeaderClause {
                 Ok(HeaderClause::SavedBy(Box::new(UnquotedString::new(pv.val))))
             }
             rdfs::COMMENT => Ok(HeaderClause::Remark(Box::new(UnquotedString::new(pv.val)))),
-            other => {
-                let rel = RelationIdent::from_str(&other)?;
-                let pv = match Ident::from_str(&pv.val) {
-                    Ok(id) => PropertyValue::from(ResourcePropertyValue::new(rel, id)),
-                    Err(_) => PropertyValue::from(LiteralPropertyValue::new(
-                        rel,
-                        QuotedString::new(pv.val),
-                        Ident::from(PrefixedIdent::new("xsd", "string")),
-                    )),
-                };
+            _ => {
+                let pv = PropertyValue::from_graph(pv)?;
                 Ok(HeaderClause::PropertyValue(Box::new(pv)))
             }
         }
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use super::Context;
+use super::Diagnostic;
+use super::TypeInference;
+use crate::constants::property::rdf;
+use crate::model::Graph;
+use crate::model::NodeType;
+
+/// Check whether an edge predicate stands for `rdf:type`.
+pub fn is_rdf_type(pred: &str) -> bool {
+    matches!(pred, "type" | "rdf:type" | rdf::TYPE)
+}
+
+/// Record a type for `id`, unless one was already recorded.
+fn record<'g>(types: &mut HashMap<&'g str, NodeType>, id: &'g str, ty: NodeType) {
+    types.entry(id).or_insert(ty);
+}
+
+/// Collect the types implied by the usage of nodes in the graph edges.
+fn edge_types(graph: &Graph) -> HashMap<&str, NodeType> {
+    let mut types = HashMap::new();
+    for edge in graph.edges.iter() {
+        types.insert(edge.pred.as_str(), NodeType::Property);
+    }
+    for edge in graph.edges.iter().filter(|e| is_rdf_type(&e.pred)) {
+        record(&mut types, &edge.sub, NodeType::Individual);
+        record(&mut types, &edge.obj, NodeType::Class);
+    }
+    types
+}
+
+/// Collect the types implied by the usage of nodes in the graph axioms.
+fn axiom_types(graph: &Graph) -> HashMap<&str, NodeType> {
+    let mut types = HashMap::new();
+    for dr in graph.domain_range_axioms.iter() {
+        types.insert(dr.predicate_id.as_str(), NodeType::Property);
+    }
+    for pc in graph.property_chain_axioms.iter() {
+        types.insert(pc.predicate_id.as_str(), NodeType::Property);
+        for id in pc.chain_predicate_ids.iter() {
+            types.insert(id.as_str(), NodeType::Property);
+        }
+    }
+    for ld in graph.logical_definition_axioms.iter() {
+        for r in ld.restrictions.iter() {
+            types.insert(r.property_id.as_str(), NodeType::Property);
+        }
+    }
+
+    for dr in graph.domain_range_axioms.iter() {
+        for id in dr.domain_class_ids.iter().chain(&dr.range_class_ids) {
+            record(&mut types, id, NodeType::Class);
+        }
+    }
+    for ld in graph.logical_definition_axioms.iter() {
+        record(&mut types, &ld.defined_class_id, NodeType::Class);
+        for id in ld.genus_ids.iter() {
+            record(&mut types, id, NodeType::Class);
+        }
+        for r in ld.restrictions.iter() {
+            record(&mut types, &r.filler_id, NodeType::Class);
+        }
+    }
+    types
+}
+
+/// Give a type to the untyped nodes of `graph` using the configured strategies.
+pub fn infer_types(graph: &mut Graph, ctx: &mut Context) {
+    let strategies = &ctx.config.type_inference;
+    if strategies.is_empty() || graph.nodes.iter().all(|n| n.ty.is_some()) {
+        return;
+    }
+
+    // only collect usages for the strategies that need them
+    let mut inferred = Vec::new();
+    {
+        let edges = if strategies.contains(&TypeInference::Edges) {
+            edge_types(graph)
+        } else {
+            HashMap::new()
+        };
+        let axioms = if strategies.contains(&TypeInference::Axioms) {
+            axiom_types(graph)
+        } else {
+            HashMap::new()
+        };
+
+        for (i, node) in graph.nodes.iter().enumerate() {
+            if node.ty.is_some() {
+                continue;
+            }
+            let id = node.id.as_str();
+            let found = strategies.iter().find_map(|strategy| {
+                let ty = match strategy {
+                    TypeInference::Default(ty) => Some(*ty),
+                    TypeInference::Edges => edges.get(id).copied(),
+                    TypeInference::Axioms => axioms.get(id).copied(),
+                };
+                ty.map(|ty| (ty, strategy))
+            });
+            if let Some((ty, strategy)) = found {
+                ctx.diagnostics.push(Diagnostic::InferredType {
+                    id: node.id.clone(),
+                    ty,
+                    strategy: strategy.clone(),
+                });
+                inferred.push((i, ty));
+            }
+        }
+    }
+
+    for (i, ty) in inferred {
+        graph.nodes[i].ty = Some(ty);
+    }
+}
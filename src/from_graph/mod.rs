@@ -1,13 +1,131 @@
+//! Conversion of OBO graphs into OBO documents.
+
 mod doc;
+mod edge;
 mod entity;
 mod header;
+mod infer;
+mod pv;
 mod syn;
 mod xref;
 
+use std::str::FromStr;
+
+use fastobo::error::SyntaxError;
+
+use crate::error::Error;
 use crate::error::Result;
+use crate::model::NodeType;
 
 /// Trait to convert an OBO graph element into an OBO syntax node.
 #[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
 pub trait FromGraph<T>: Sized {
     fn from_graph(source: T) -> Result<Self>;
 }
+
+/// Trait to convert an OBO graph element using a conversion context.
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+pub trait FromGraphCtx<T>: Sized {
+    fn from_graph_ctx(source: T, ctx: &mut Context) -> Result<Self>;
+}
+
+/// Parse an identifier, reporting where it was found on failure.
+pub(crate) fn parse_ident<T>(context: &str, value: &str) -> Result<T>
+where
+    T: FromStr<Err = SyntaxError>,
+{
+    T::from_str(value).map_err(|e| Error::invalid_identifier(context, value, e))
+}
+
+// ---------------------------------------------------------------------------
+
+/// A strategy to infer the type of a node declared without a `type`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeInference {
+    /// Use the given type for every untyped node.
+    Default(NodeType),
+    /// Infer the type from the usage of the node in the graph edges.
+    ///
+    /// A node used as the predicate of an edge is a `PROPERTY`, a node used
+    /// as the subject of an `rdf:type` edge is an `INDIVIDUAL`, and a node
+    /// used as the object of an `rdf:type` edge is a `CLASS`.
+    Edges,
+    /// Infer the type from the usage of the node in the graph axioms.
+    ///
+    /// A node used as the predicate of a `DomainRangeAxiom`, as the property
+    /// of a `PropertyChainAxiom` or of an existential restriction is a
+    /// `PROPERTY`, and a node used as a domain, a range, a genus or a filler
+    /// is a `CLASS`.
+    Axioms,
+}
+
+/// A policy for edges referring to nodes absent from the converted graph.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DanglingEdges {
+    /// Keep edges with an undeclared object, drop edges with an undeclared
+    /// subject with a diagnostic.
+    #[default]
+    Keep,
+    /// Drop edges with an undeclared subject or object with a diagnostic.
+    Drop,
+    /// Create a stub `[Term]` or `[Typedef]` frame for undeclared nodes,
+    /// including the predicates of `relationship` clauses.
+    Stub,
+    /// Fail the conversion with an `Error::DanglingEdge`.
+    Error,
+}
+
+/// The configuration of a graph to document conversion.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// The strategies used to infer the type of untyped nodes, in order.
+    ///
+    /// Untyped nodes for which no strategy succeeds are skipped.
+    pub type_inference: Vec<TypeInference>,
+    /// The policy for edges referring to undeclared nodes.
+    pub dangling_edges: DanglingEdges,
+}
+
+/// A notable event that happened during a conversion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// An untyped node was given a type by an inference strategy.
+    InferredType {
+        id: String,
+        ty: NodeType,
+        strategy: TypeInference,
+    },
+    /// An untyped node was skipped since its type could not be inferred.
+    UntypedNode { id: String },
+    /// An edge was dropped since it refers to an undeclared node.
+    DanglingEdge {
+        sub: String,
+        pred: String,
+        obj: String,
+    },
+    /// A stub frame was created for an undeclared node.
+    StubFrame { id: String },
+}
+
+/// The context of a graph to document conversion.
+#[derive(Clone, Debug, Default)]
+pub struct Context {
+    pub config: Config,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Context {
+    /// Create a new conversion context using the given configuration.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+impl From<Config> for Context {
+    fn from(config: Config) -> Self {
+        Self::new(config)
+    }
+}
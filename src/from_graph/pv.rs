@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use fastobo::ast::Ident;
+use fastobo::ast::LiteralPropertyValue;
+use fastobo::ast::PrefixedIdent;
+use fastobo::ast::PropertyValue;
+use fastobo::ast::QuotedString;
+use fastobo::ast::RelationIdent;
+use fastobo::ast::ResourcePropertyValue;
+
+use super::parse_ident;
+use super::FromGraph;
+use crate::constants::uri;
+use crate::error::Result;
+use crate::model::BasicPropertyValue;
+
+impl FromGraph<BasicPropertyValue> for PropertyValue {
+    fn from_graph(pv: BasicPropertyValue) -> Result<Self> {
+        let rel = parse_ident::<RelationIdent>("property value", &pv.pred)?;
+        match pv.val_type {
+            // use the datatype to build a literal when there is one
+            Some(dt) => {
+                let datatype = match dt.strip_prefix(uri::XSD) {
+                    Some(local) => Ident::from(PrefixedIdent::new("xsd", local)),
+                    None => parse_ident::<Ident>("datatype", &dt)?,
+                };
+                let literal = QuotedString::new(pv.val);
+                Ok(LiteralPropertyValue::new(rel, literal, datatype).into())
+            }
+            // otherwise guess whether the value is a resource or a string
+            None => match Ident::from_str(&pv.val) {
+                Ok(id) => Ok(ResourcePropertyValue::new(rel, id).into()),
+                Err(_) => Ok(LiteralPropertyValue::new(
+                    rel,
+                    QuotedString::new(pv.val),
+                    Ident::from(PrefixedIdent::new("xsd", "string")),
+                )
+                .into()),
+            },
+        }
+    }
+}
@@ -1,5 +1,3 @@
-use std::str::FromStr;
-
 use fastobo::ast::Ident;
 use fastobo::ast::QuotedString;
 use fastobo::ast::Synonym;
@@ -7,6 +5,7 @@ use fastobo::ast::SynonymScope;
 use fastobo::ast::Xref;
 use fastobo::ast::XrefList;
 
+use super::parse_ident;
 use super::FromGraph;
 use crate::error::Error;
 use crate::error::Result;
@@ -25,7 +24,7 @@ impl FromGraph<SynonymPropertyValue> for Synonym {
         let xrefs = pv
             .xrefs
             .into_iter()
-            .map(|id| Ident::from_str(&id).map(Xref::new).map_err(Error::from))
+            .map(|id| parse_ident::<Ident>("synonym xref", &id).map(Xref::new))
             .collect::<Result<XrefList>>()?;
         Ok(Synonym::with_xrefs(desc, scope, xrefs))
     }
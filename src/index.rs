@@ -0,0 +1,152 @@
+//! Indexed access to the elements of an OBO graph.
+//!
+//! A [`Graph`] stores its nodes and edges in flat vectors, so looking up a
+//! node or the edges of a node requires a linear scan. An [`IndexedGraph`]
+//! borrows a graph and maps identifiers to the positions of the matching
+//! elements.
+
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use crate::model::Edge;
+use crate::model::Graph;
+use crate::model::Node;
+
+/// A view over a graph with constant-time lookups of nodes and edges.
+///
+/// The index borrows the graph it was built from, so the graph cannot be
+/// modified while the index is alive. To take changes into account, drop
+/// the index, edit the graph, and build a new index with
+/// [`IndexedGraph::new`].
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// # use fastobo_graphs::index::IndexedGraph;
+/// let doc = fastobo_graphs::from_str(r#"{"graphs": [{
+///     "id": "test",
+///     "nodes": [{"id": "A"}, {"id": "B"}],
+///     "edges": [{"sub": "A", "pred": "is_a", "obj": "B"}]
+/// }]}"#).unwrap();
+/// let index = IndexedGraph::new(&doc.graphs[0]);
+/// assert!(index.node("A").is_some());
+/// assert_eq!(index.incoming("B").map(|e| e.sub.as_str()).collect::<Vec<_>>(), ["A"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexedGraph<'a> {
+    graph: &'a Graph,
+    nodes: HashMap<&'a str, usize>,
+    outgoing: HashMap<&'a str, Vec<usize>>,
+    incoming: HashMap<&'a str, Vec<usize>>,
+    predicates: HashMap<&'a str, Vec<usize>>,
+    xrefs: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> IndexedGraph<'a> {
+    /// Index the given graph.
+    pub fn new(graph: &'a Graph) -> Self {
+        let mut nodes = HashMap::new();
+        let mut xrefs: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, node) in graph.nodes.iter().enumerate() {
+            // keep the first declaration of nodes declared several times
+            nodes.entry(node.id.as_str()).or_insert(i);
+            if let Some(meta) = &node.meta {
+                for xref in meta.xrefs.iter() {
+                    let positions = xrefs.entry(xref.val.as_str()).or_default();
+                    if positions.last() != Some(&i) {
+                        positions.push(i);
+                    }
+                }
+            }
+        }
+
+        let mut outgoing: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut incoming: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut predicates: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, edge) in graph.edges.iter().enumerate() {
+            outgoing.entry(edge.sub.as_str()).or_default().push(i);
+            incoming.entry(edge.obj.as_str()).or_default().push(i);
+            predicates.entry(edge.pred.as_str()).or_default().push(i);
+        }
+
+        Self {
+            graph,
+            nodes,
+            outgoing,
+            incoming,
+            predicates,
+            xrefs,
+        }
+    }
+
+    /// Get a reference to the indexed graph.
+    pub fn graph(&self) -> &'a Graph {
+        self.graph
+    }
+
+    /// Check whether a node with the given identifier is declared.
+    pub fn contains_node(&self, id: &str) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Get the node with the given identifier, if any.
+    ///
+    /// If several nodes share the same identifier, the first one is returned.
+    pub fn node(&self, id: &str) -> Option<&'a Node> {
+        let graph = self.graph;
+        self.nodes.get(id).map(|&i| &graph.nodes[i])
+    }
+
+    /// Iterate over the nodes with an xref to the given identifier.
+    pub fn nodes_with_xref<'i>(&'i self, xref: &str) -> impl Iterator<Item = &'a Node> + 'i {
+        let graph = self.graph;
+        let positions = self.xrefs.get(xref).map(Vec::as_slice).unwrap_or(&[]);
+        positions.iter().map(move |&i| &graph.nodes[i])
+    }
+
+    /// Iterate over the edges with the given node as their subject.
+    pub fn outgoing<'i>(&'i self, id: &str) -> impl Iterator<Item = &'a Edge> + 'i {
+        self.edges(self.outgoing.get(id))
+    }
+
+    /// Iterate over the edges with the given node as their object.
+    pub fn incoming<'i>(&'i self, id: &str) -> impl Iterator<Item = &'a Edge> + 'i {
+        self.edges(self.incoming.get(id))
+    }
+
+    /// Iterate over the edges with the given predicate.
+    ///
+    /// Predicates are compared verbatim, so a CURIE and the equivalent IRI
+    /// are considered different predicates.
+    pub fn edges_with_predicate<'i>(&'i self, pred: &str) -> impl Iterator<Item = &'a Edge> + 'i {
+        self.edges(self.predicates.get(pred))
+    }
+
+    fn edges<'i>(
+        &'i self,
+        positions: Option<&'i Vec<usize>>,
+    ) -> impl Iterator<Item = &'a Edge> + 'i {
+        let graph = self.graph;
+        let positions = positions.map(Vec::as_slice).unwrap_or(&[]);
+        positions.iter().map(move |&i| &graph.edges[i])
+    }
+}
+
+impl AsRef<Graph> for IndexedGraph<'_> {
+    fn as_ref(&self) -> &Graph {
+        self.graph
+    }
+}
+
+impl Deref for IndexedGraph<'_> {
+    type Target = Graph;
+    fn deref(&self) -> &Graph {
+        self.graph
+    }
+}
+
+impl<'a> From<&'a Graph> for IndexedGraph<'a> {
+    fn from(graph: &'a Graph) -> Self {
+        Self::new(graph)
+    }
+}
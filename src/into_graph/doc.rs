@@ -1,8 +1,10 @@
-use std::mem::replace;
+use std::mem::take;
 
 use fastobo::ast::HeaderFrame;
 use fastobo::ast::OboDoc;
 
+use super::header_prefixes;
+use super::xrefs::TreatXrefs;
 use super::Context;
 use super::IntoGraph;
 use super::IntoGraphCtx;
@@ -17,33 +19,34 @@ impl IntoGraphCtx<GraphDocument> for OboDoc {
         self.treat_xrefs();
 
         // Take ownership over the header and the entities.
-        let header = replace(self.header_mut(), HeaderFrame::default());
-        let entities = replace(self.entities_mut(), Vec::new());
-
-        // Build the empty graph
-        let mut graph = Graph {
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            id: ctx.ontology_iri.to_string(),
-            label: None,
-            meta: header.into_graph_ctx(ctx).map(Box::new).map(Some)?,
-            equivalent_nodes_sets: Vec::new(),
-            logical_definition_axioms: Vec::new(),
-            domain_range_axioms: Vec::new(),
-            property_chain_axioms: Vec::new(),
-        };
+        let header = take(self.header_mut());
+        let entities = take(self.entities_mut());
 
         // Extend the graph with all entities
+        let mut graph = empty_graph(&header, ctx)?;
         for entity in entities.into_iter() {
-            // let mut entity_graph = entity.into_graph_ctx(ctx)?;
-            // graph.extend(entity_graph);
             let entity_graph = entity.into_graph_ctx(ctx)?;
             graph.extend(entity_graph);
         }
 
-        // TODO: Add imports recursively
-        // for clause in header.iter() {}
-        Ok(GraphDocument::from(graph))
+        Ok(document(graph, &header))
+    }
+}
+
+impl IntoGraphCtx<GraphDocument> for &OboDoc {
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<GraphDocument> {
+        // Record the clauses added by *treat-xrefs* macros, since the
+        // document cannot be preprocessed in place.
+        let xrefs = TreatXrefs::new(self.header(), self.entities());
+
+        // Extend the graph with all entities
+        let mut graph = empty_graph(self.header(), ctx)?;
+        for (i, entity) in self.entities().iter().enumerate() {
+            let entity_graph = xrefs.convert(i, entity, ctx)?;
+            graph.extend(entity_graph);
+        }
+
+        Ok(document(graph, self.header()))
     }
 }
 
@@ -54,3 +57,38 @@ impl IntoGraph for OboDoc {
         self.into_graph_ctx(&mut ctx)
     }
 }
+
+impl IntoGraph for &OboDoc {
+    #[inline]
+    fn into_graph(self) -> Result<GraphDocument> {
+        let mut ctx = Context::from(self);
+        self.into_graph_ctx(&mut ctx)
+    }
+}
+
+/// Build the empty graph of a document with the given header.
+fn empty_graph(header: &HeaderFrame, ctx: &mut Context) -> Result<Graph> {
+    Ok(Graph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+        id: ctx.ontology_iri.to_string(),
+        label: None,
+        meta: header.into_graph_ctx(ctx).map(Box::new).map(Some)?,
+        equivalent_nodes_sets: Vec::new(),
+        logical_definition_axioms: Vec::new(),
+        domain_range_axioms: Vec::new(),
+        property_chain_axioms: Vec::new(),
+        extra: Default::default(),
+    })
+}
+
+/// Wrap the graph of a document with the given header into a graph document.
+fn document(graph: Graph, header: &HeaderFrame) -> GraphDocument {
+    // Record the ID spaces declared in the header as prefixes
+    let mut doc = GraphDocument::from(graph);
+    doc.prefixes = header_prefixes(header);
+
+    // TODO: Add imports recursively
+    // for clause in header.iter() {}
+    doc
+}
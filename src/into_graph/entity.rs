@@ -1,11 +1,16 @@
 use fastobo::ast::EntityFrame;
+use fastobo::ast::Ident;
 use fastobo::ast::InstanceFrame;
+use fastobo::ast::Line;
+use fastobo::ast::TermClause;
 use fastobo::ast::TermFrame;
+use fastobo::ast::TypedefClause;
 use fastobo::ast::TypedefFrame;
 
 use super::Context;
 use super::IntoGraphCtx;
 use crate::constants::property::obo_in_owl;
+use crate::constants::uri;
 use crate::error::Result;
 use crate::model::BasicPropertyValue;
 use crate::model::DefinitionPropertyValue;
@@ -15,6 +20,7 @@ use crate::model::Graph;
 use crate::model::Meta;
 use crate::model::Node;
 use crate::model::NodeType;
+use crate::model::PropertyType;
 use crate::model::XrefPropertyValue;
 
 // ---------------------------------------------------------------------------
@@ -23,6 +29,7 @@ macro_rules! impl_frame_common {
     (
         $ctx:ident,
         $clause:ident,
+        $qualifiers:ident,
         $node:ident,
         $edges:ident,
         $meta:ident,
@@ -32,7 +39,7 @@ macro_rules! impl_frame_common {
         match $clause {
             IsAnonymous(val) => (),
             Name(name) => {
-                $node.label = Some(name.into_string());
+                $node.label = Some(name.as_str().to_string());
             }
             Namespace(ns) => {
                 $meta.basic_property_values.push(
@@ -51,12 +58,37 @@ macro_rules! impl_frame_common {
                 );
             }
             Def(def) => {
+                // keep xref descriptions and qualifiers as definition metadata
+                let mut def_meta = Meta::default();
+                for xref in def.xrefs().iter() {
+                    if let Some(desc) = xref.description() {
+                        def_meta.xrefs.push(XrefPropertyValue {
+                            pred: None,
+                            val: $ctx.expand(xref.id()),
+                            xrefs: Vec::new(),
+                            meta: None,
+                            label: Some(desc.as_str().to_string()),
+                            extra: Default::default(),
+                        });
+                    }
+                }
+                for qualifier in $qualifiers.iter().flat_map(|q| q.iter()) {
+                    // unprefixed qualifiers are OBO in OWL annotation properties
+                    let key = match qualifier.key().as_ref() {
+                        Ident::Unprefixed(unp) => format!("{}{}", uri::OBO_IN_OWL, unp),
+                        other => $ctx.expand(other),
+                    };
+                    def_meta.basic_property_values.push(
+                        BasicPropertyValue::new(key, qualifier.value().as_str().to_string())
+                    );
+                }
                 $meta.definition = Some(Box::new(
                     DefinitionPropertyValue {
                         pred: None,
                         val: def.text().as_str().to_string(),
                         xrefs: def.xrefs().iter().map(|x| $ctx.expand(x.id())).collect(),
-                        meta: None
+                        meta: Some(Box::new(def_meta)).filter(|m| **m != Meta::default()),
+                        extra: Default::default(),
                     }
                 ))
             }
@@ -70,19 +102,23 @@ macro_rules! impl_frame_common {
                         val:  $ctx.expand(xref.id()),
                         xrefs: Vec::new(),
                         meta: None,
-                        label: xref.description().map(|d| d.clone().into_string()),
+                        label: xref.description().map(|d| d.as_str().to_string()),
+                        extra: Default::default(),
                     }
-                )
+                );
             }
             Builtin(bool) => {}
-            PropertyValue(pv) => {}
+            PropertyValue(pv) => {
+                $meta.basic_property_values.push((&**pv).into_graph_ctx($ctx)?);
+            }
             IsA(id) => {
                 $edges.push(
                     Edge {
                         sub: $current.clone(),
                         pred: String::from("is_a"),
-                        obj: $ctx.expand(*id),
+                        obj: $ctx.expand(&**id),
                         meta: None,
+                        extra: Default::default(),
                     }
                 );
             }
@@ -93,9 +129,10 @@ macro_rules! impl_frame_common {
                 $edges.push(
                     Edge {
                         sub: $current.clone(),
-                        pred: $ctx.expand(*rid),
-                        obj: $ctx.expand(*cid),
+                        pred: $ctx.expand(&**rid),
+                        obj: $ctx.expand(&**cid),
                         meta: None,
+                        extra: Default::default(),
                     }
                 )
             }
@@ -103,7 +140,7 @@ macro_rules! impl_frame_common {
                 $meta.basic_property_values.push(
                     BasicPropertyValue::new(
                         obo_in_owl::CREATED_BY.to_string(),
-                        name.into_string(),
+                        name.as_str().to_string(),
                     )
                 );
             }
@@ -120,7 +157,7 @@ macro_rules! impl_frame_common {
                 $meta.basic_property_values.push(
                     BasicPropertyValue::new(
                         obo_in_owl::REPLACED_BY.to_string(),
-                        $ctx.expand(*cid),
+                        $ctx.expand(&**cid),
                     )
                 );
             }
@@ -128,7 +165,7 @@ macro_rules! impl_frame_common {
                 $meta.basic_property_values.push(
                     BasicPropertyValue::new(
                         obo_in_owl::CONSIDER.to_string(),
-                        $ctx.expand(*cid),
+                        $ctx.expand(&**cid),
                     )
                 );
             }
@@ -140,11 +177,18 @@ macro_rules! impl_frame_common {
 // ---------------------------------------------------------------------------
 
 impl IntoGraphCtx<Graph> for EntityFrame {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
+        (&self).into_graph_ctx(ctx)
+    }
+}
+
+impl IntoGraphCtx<Graph> for &EntityFrame {
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
         match self {
-            EntityFrame::Term(t) => t.into_graph_ctx(ctx),
-            EntityFrame::Typedef(t) => t.into_graph_ctx(ctx),
-            EntityFrame::Instance(t) => t.into_graph_ctx(ctx),
+            EntityFrame::Term(t) => t.as_ref().into_graph_ctx(ctx),
+            EntityFrame::Typedef(t) => t.as_ref().into_graph_ctx(ctx),
+            EntityFrame::Instance(t) => t.as_ref().into_graph_ctx(ctx),
         }
     }
 }
@@ -152,154 +196,232 @@ impl IntoGraphCtx<Graph> for EntityFrame {
 // ---------------------------------------------------------------------------
 
 impl IntoGraphCtx<Graph> for TermFrame {
+    #[inline]
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
-        use fastobo::ast::TermClause::*;
+        (&self).into_graph_ctx(ctx)
+    }
+}
 
-        //
-        let mut edges = Vec::new();
-        let mut meta = Meta::default();
-        let mut node = Node {
-            id: ctx.expand(self.id().as_inner()),
-            meta: None,
-            ty: Some(NodeType::Class),
-            label: None,
-        };
+impl IntoGraphCtx<Graph> for &TermFrame {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
+        term_graph(self, &[], ctx)
+    }
+}
 
-        //
-        let current_id = ctx.expand(self.id().as_inner());
-        for line in self.into_iter() {
-            let clause = line.into_inner();
-            impl_frame_common!(ctx, clause, node, edges, meta, current_id,
-                IntersectionOf(optrid, cid) => {}
-            );
-        }
+/// Convert a term frame, followed by additional clauses.
+pub(crate) fn term_graph(
+    frame: &TermFrame,
+    extra: &[Line<TermClause>],
+    ctx: &mut Context,
+) -> Result<Graph> {
+    use fastobo::ast::TermClause::*;
 
-        //
-        node.meta = Some(Box::new(meta));
-        Ok(Graph {
-            id: node.id.clone(),
-            nodes: vec![node],
-            edges,
-            label: None,
-            meta: Some(Box::new(Meta::default())),
-            equivalent_nodes_sets: Vec::new(),
-            logical_definition_axioms: Vec::new(),
-            domain_range_axioms: Vec::new(),
-            property_chain_axioms: Vec::new(),
-        })
+    //
+    let mut edges = Vec::new();
+    let mut meta = Meta::default();
+    let mut node = Node {
+        id: ctx.expand(frame.id().as_inner()),
+        meta: None,
+        ty: Some(NodeType::Class),
+        label: None,
+        property_type: None,
+        extra: Default::default(),
+    };
+
+    //
+    let current_id = ctx.expand(frame.id().as_inner());
+    for line in frame.iter().chain(extra) {
+        let qualifiers = line.qualifiers();
+        let clause = line.as_inner();
+        impl_frame_common!(ctx, clause, qualifiers, node, edges, meta, current_id,
+            IntersectionOf(optrid, cid) => {}
+        );
     }
+
+    //
+    node.meta = Some(Box::new(meta));
+    Ok(Graph {
+        id: node.id.clone(),
+        nodes: vec![node],
+        edges,
+        label: None,
+        meta: Some(Box::new(Meta::default())),
+        equivalent_nodes_sets: Vec::new(),
+        logical_definition_axioms: Vec::new(),
+        domain_range_axioms: Vec::new(),
+        property_chain_axioms: Vec::new(),
+        extra: Default::default(),
+    })
 }
 
 // ---------------------------------------------------------------------------
 
 impl IntoGraphCtx<Graph> for TypedefFrame {
+    #[inline]
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
-        use fastobo::ast::TypedefClause::*;
+        (&self).into_graph_ctx(ctx)
+    }
+}
 
-        let mut edges = Vec::new();
-        let mut meta = Meta::default();
-        let mut node = Node {
-            id: ctx.expand(self.id().as_inner()),
-            meta: None,
-            ty: Some(NodeType::Property),
-            label: None,
-        };
-        let mut dra = Vec::with_capacity(1);
+impl IntoGraphCtx<Graph> for &TypedefFrame {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
+        typedef_graph(self, &[], ctx)
+    }
+}
 
-        let current_id = ctx.expand(self.id().as_inner());
-        for line in self.into_iter() {
-            let clause = line.into_inner();
-            impl_frame_common!(ctx, clause, node, edges, meta, current_id,
-                Domain(id) => {
-                    if dra.is_empty() {
-                        dra.push(DomainRangeAxiom {
-                            meta: None,
-                            predicate_id: current_id.clone(),
-                            domain_class_ids: Vec::new(),
-                            range_class_ids: Vec::new(),
-                            all_values_from_edges: Vec::new(),
-                        });
-                    }
-                    dra[0].domain_class_ids.push(ctx.expand(*id));
-                },
-                Range(id) => {
-                    if dra.is_empty() {
-                        dra.push(DomainRangeAxiom {
-                            meta: None,
-                            predicate_id: current_id.clone(),
-                            domain_class_ids: Vec::new(),
-                            range_class_ids: Vec::new(),
-                            all_values_from_edges: Vec::new(),
-                        });
-                    }
-                    dra[0].range_class_ids.push(ctx.expand(*id));
-                },
-                HoldsOverChain(r1, r2) => {},
-                IsAntiSymmetric(b) => {},
-                IsCyclic(b) => {
-                    meta.basic_property_values.push(
-                        BasicPropertyValue::new(
-                            obo_in_owl::IS_CYCLIC.to_string(),
-                            b.to_string(),
-                        )
-                    );
-                },
-                IsReflexive(b) => {},
-                IsSymmetric(b) => {},
-                IsAsymmetric(b) => {},
-                IsTransitive(b) => {},
-                IsFunctional(b) => {},
-                IsInverseFunctional(b) => {},
-                IntersectionOf(rid) => {},
-                InverseOf(r) => {},
-                TransitiveOver(r) => {},
-                EquivalentToChain(r1, r2) => {},
-                DisjointOver(r) => {
-                    meta.basic_property_values.push(
-                        BasicPropertyValue::new(
-                            obo_in_owl::DISJOINT_OVER.to_string(),
-                            ctx.expand(*r),
-                        )
-                    );
-                },
-                ExpandAssertionTo(desc, xrefs) => {},
-                ExpandExpressionTo(desc, xrefs) => {},
-                IsMetadataTag(b) => {
-                    meta.basic_property_values.push(
-                        BasicPropertyValue::new(
-                            obo_in_owl::IS_METADATA_TAG.to_string(),
-                            b.to_string(),
-                        )
-                    );
-                },
-                IsClassLevel(b) => {
-                    meta.basic_property_values.push(
-                        BasicPropertyValue::new(
-                            obo_in_owl::IS_CLASS_LEVEL.to_string(),
-                            b.to_string(),
-                        )
-                    );
-                }
-            );
-        }
+/// Convert a typedef frame, followed by additional clauses.
+pub(crate) fn typedef_graph(
+    frame: &TypedefFrame,
+    extra: &[Line<TypedefClause>],
+    ctx: &mut Context,
+) -> Result<Graph> {
+    use fastobo::ast::TypedefClause::*;
 
-        Ok(Graph {
-            edges,
-            id: node.id.clone(),
-            nodes: vec![node],
-            label: None,
-            meta: Some(Box::new(Meta::default())),
-            domain_range_axioms: dra,
-            equivalent_nodes_sets: Vec::new(),
-            logical_definition_axioms: Vec::new(),
-            property_chain_axioms: Vec::new(),
-        })
+    let lines = frame.iter().chain(extra).collect::<Vec<_>>();
+    let mut edges = Vec::new();
+    let mut meta = Meta::default();
+    let mut node = Node {
+        id: ctx.expand(frame.id().as_inner()),
+        meta: None,
+        ty: Some(NodeType::Property),
+        label: None,
+        property_type: None,
+        extra: Default::default(),
+    };
+    let mut dra = Vec::with_capacity(1);
+
+    let current_id = ctx.expand(frame.id().as_inner());
+    for line in lines.iter() {
+        let qualifiers = line.qualifiers();
+        let clause = line.as_inner();
+        impl_frame_common!(ctx, clause, qualifiers, node, edges, meta, current_id,
+            Domain(id) => {
+                if dra.is_empty() {
+                    dra.push(DomainRangeAxiom {
+                        meta: None,
+                        predicate_id: current_id.clone(),
+                        domain_class_ids: Vec::new(),
+                        range_class_ids: Vec::new(),
+                        all_values_from_edges: Vec::new(),
+                        extra: Default::default(),
+                    });
+                }
+                dra[0].domain_class_ids.push(ctx.expand(&**id));
+            },
+            Range(id) => {
+                if dra.is_empty() {
+                    dra.push(DomainRangeAxiom {
+                        meta: None,
+                        predicate_id: current_id.clone(),
+                        domain_class_ids: Vec::new(),
+                        range_class_ids: Vec::new(),
+                        all_values_from_edges: Vec::new(),
+                        extra: Default::default(),
+                    });
+                }
+                dra[0].range_class_ids.push(ctx.expand(&**id));
+            },
+            HoldsOverChain(r1, r2) => {},
+            IsAntiSymmetric(b) => {},
+            IsCyclic(b) => {
+                meta.basic_property_values.push(
+                    BasicPropertyValue::new(
+                        obo_in_owl::IS_CYCLIC.to_string(),
+                        b.to_string(),
+                    )
+                );
+            },
+            IsReflexive(b) => {},
+            IsSymmetric(b) => {},
+            IsAsymmetric(b) => {},
+            IsTransitive(b) => {
+                meta.basic_property_values.push(
+                    BasicPropertyValue::new(
+                        obo_in_owl::IS_TRANSITIVE.to_string(),
+                        b.to_string(),
+                    )
+                );
+            },
+            IsFunctional(b) => {},
+            IsInverseFunctional(b) => {},
+            IntersectionOf(rid) => {},
+            InverseOf(r) => {},
+            TransitiveOver(r) => {},
+            EquivalentToChain(r1, r2) => {},
+            DisjointOver(r) => {
+                meta.basic_property_values.push(
+                    BasicPropertyValue::new(
+                        obo_in_owl::DISJOINT_OVER.to_string(),
+                        ctx.expand(&**r),
+                    )
+                );
+            },
+            ExpandAssertionTo(desc, xrefs) => {},
+            ExpandExpressionTo(desc, xrefs) => {},
+            IsMetadataTag(b) => {
+                meta.basic_property_values.push(
+                    BasicPropertyValue::new(
+                        obo_in_owl::IS_METADATA_TAG.to_string(),
+                        b.to_string(),
+                    )
+                );
+            },
+            IsClassLevel(b) => {
+                meta.basic_property_values.push(
+                    BasicPropertyValue::new(
+                        obo_in_owl::IS_CLASS_LEVEL.to_string(),
+                        b.to_string(),
+                    )
+                );
+            }
+        );
     }
+
+    // infer the OWL property type from the typedef semantics
+    let property_type = if lines
+        .iter()
+        .any(|line| matches!(line.as_inner(), IsMetadataTag(true) | IsClassLevel(true)))
+    {
+        PropertyType::Annotation
+    } else if lines.iter().any(|line| match line.as_inner() {
+        Range(id) => ctx.expand(&**id).starts_with(uri::XSD),
+        _ => false,
+    }) {
+        PropertyType::Data
+    } else {
+        PropertyType::Object
+    };
+
+    //
+    node.property_type = Some(property_type);
+    node.meta = Some(Box::new(meta));
+
+    Ok(Graph {
+        edges,
+        id: node.id.clone(),
+        nodes: vec![node],
+        label: None,
+        meta: Some(Box::new(Meta::default())),
+        domain_range_axioms: dra,
+        equivalent_nodes_sets: Vec::new(),
+        logical_definition_axioms: Vec::new(),
+        property_chain_axioms: Vec::new(),
+        extra: Default::default(),
+    })
 }
 
 // ---------------------------------------------------------------------------
 
 impl IntoGraphCtx<Graph> for InstanceFrame {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
+        (&self).into_graph_ctx(ctx)
+    }
+}
+
+impl IntoGraphCtx<Graph> for &InstanceFrame {
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<Graph> {
         // ... TODO ... //
         Ok(Graph {
@@ -312,6 +434,7 @@ impl IntoGraphCtx<Graph> for InstanceFrame {
             logical_definition_axioms: Vec::new(),
             domain_range_axioms: Vec::new(),
             property_chain_axioms: Vec::new(),
+            extra: Default::default(),
         })
     }
 }
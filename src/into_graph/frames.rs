@@ -0,0 +1,79 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+
+use fastobo::ast::Frame;
+use fastobo::ast::HeaderFrame;
+
+use super::header_prefixes;
+use super::xrefs::TreatXrefs;
+use super::Context;
+use super::IntoGraphCtx;
+use crate::error::Result;
+use crate::model::Graph;
+use crate::stream::GraphWriter;
+
+/// Convert a sequence of OBO frames into a JSON graph document.
+///
+/// Frames are converted and written one at a time, so that the document
+/// never has to be loaded in memory. The frames are expected to start with
+/// a header frame, as yielded by the parsers of the `fastobo` crate.
+///
+/// Nodes are written directly while edges are written to the `spill`
+/// stream, and copied to the writer once all frames have been converted.
+/// Pass a temporary file to keep memory use bounded by the size of a frame,
+/// since an in-memory spill such as a `Cursor<Vec<u8>>` holds every edge
+/// of the ontology:
+/// ```rust
+/// # extern crate fastobo;
+/// # extern crate fastobo_graphs;
+/// # use std::io::BufReader;
+/// # use std::io::Cursor;
+/// use fastobo::parser::Parser;
+/// use fastobo::parser::SequentialParser;
+///
+/// let obo = "ontology: test\n\n[Term]\nid: TEST:001\n";
+/// let parser = SequentialParser::new(BufReader::new(obo.as_bytes()));
+/// let spill = Cursor::new(Vec::new());
+/// let json = fastobo_graphs::into_graph::write_frames(parser, Vec::new(), spill).unwrap();
+/// let doc = fastobo_graphs::from_reader(json.as_slice()).unwrap();
+/// assert_eq!(doc.graphs[0].nodes.len(), 1);
+/// ```
+///
+/// *treat-xrefs* macros are applied to each frame on its own, so macros
+/// depending on other frames of the document (`treat-xrefs-as-has-subclass`)
+/// have no effect here.
+pub fn write_frames<I, E, W, S>(frames: I, writer: W, spill: S) -> Result<W>
+where
+    I: IntoIterator<Item = std::result::Result<Frame, E>>,
+    E: Into<crate::error::Error>,
+    W: Write,
+    S: Read + Write + Seek,
+{
+    let mut frames = frames.into_iter().peekable();
+
+    // Build the context from the header, if any.
+    let header = match frames.peek() {
+        Some(Ok(Frame::Header(_))) => match frames.next() {
+            Some(Ok(Frame::Header(h))) => *h,
+            _ => unreachable!(),
+        },
+        _ => HeaderFrame::new(),
+    };
+    let mut ctx = Context::from(&header);
+    let meta = (&header).into_graph_ctx(&mut ctx).map(Box::new)?;
+
+    // Convert the entity frames one at a time.
+    let id = ctx.ontology_iri.to_string();
+    let mut graph = GraphWriter::with_spill(writer, spill, id, Some(meta))?;
+    graph.set_prefixes(header_prefixes(&header));
+    for result in frames {
+        if let Some(entity) = result.map_err(Into::into)?.into_entity() {
+            let xrefs = TreatXrefs::new(&header, std::slice::from_ref(&entity));
+            let g: Graph = xrefs.convert(0, &entity, &mut ctx)?;
+            graph.write_graph(g)?;
+        }
+    }
+
+    graph.finish()
+}
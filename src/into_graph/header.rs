@@ -8,6 +8,13 @@ use crate::model::BasicPropertyValue;
 use crate::model::Meta;
 
 impl IntoGraphCtx<Meta> for HeaderFrame {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<Meta> {
+        (&self).into_graph_ctx(ctx)
+    }
+}
+
+impl IntoGraphCtx<Meta> for &HeaderFrame {
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<Meta> {
         use fastobo::ast::HeaderClause::*;
 
@@ -22,20 +29,20 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
 
         // extract the ontology identifier
         let id = self.iter().find_map(|clause| match clause {
-            Ontology(id) => Some(id.clone()),
+            Ontology(id) => Some(id),
             _ => None,
         });
 
-        for clause in self.into_iter() {
+        for clause in self.iter() {
             match clause {
                 FormatVersion(v) => {
                     basic_property_values.push(BasicPropertyValue::new(
                         obo_in_owl::HAS_OBO_FORMAT_VERSION.to_string(),
-                        v.into_string(),
+                        v.as_str().to_string(),
                     ));
                 }
                 DataVersion(v) => {
-                    if let Some(ref ont) = id {
+                    if let Some(ont) = id {
                         version = Some(format!(
                             "{}{}/{}/{}.owl",
                             crate::constants::uri::OBO,
@@ -45,7 +52,7 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
                         ));
                     } else {
                         // FIXME: should this be an error instead
-                        version = Some(v.into_string());
+                        version = Some(v.as_str().to_string());
                     }
                 }
                 Date(dt) => {
@@ -57,13 +64,13 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
                 SavedBy(name) => {
                     basic_property_values.push(BasicPropertyValue::new(
                         obo_in_owl::SAVED_BY.to_string(),
-                        name.into_string(),
+                        name.as_str().to_string(),
                     ));
                 }
                 AutoGeneratedBy(name) => {
                     basic_property_values.push(BasicPropertyValue::new(
                         obo_in_owl::AUTO_GENERATED_BY.to_string(),
-                        name.into_string(),
+                        name.as_str().to_string(),
                     ));
                 }
                 // NB: Import processed in the header: all imports are merged
@@ -83,7 +90,7 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
                 NamespaceIdRule(idrule) => {
                     basic_property_values.push(BasicPropertyValue::new(
                         obo_in_owl::NAMESPACE_ID_RULE.to_string(),
-                        idrule.into_string(),
+                        idrule.as_str().to_string(),
                     ));
                 }
                 Idspace(prefix, url, optdef) => (),
@@ -94,10 +101,10 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
                 TreatXrefsAsIsA(prefix) => (),
                 TreatXrefsAsHasSubclass(prefix) => (),
                 PropertyValue(pv) => {
-                    basic_property_values.push(pv.into_graph_ctx(ctx)?);
+                    basic_property_values.push(pv.as_ref().into_graph_ctx(ctx)?);
                 }
                 Remark(remark) => {
-                    comments.push(remark.into_string());
+                    comments.push(remark.as_str().to_string());
                 }
                 Ontology(ontology) => (),
                 OwlAxioms(axioms) => (),
@@ -114,6 +121,7 @@ impl IntoGraphCtx<Meta> for HeaderFrame {
             basic_property_values,
             version,
             deprecated,
+            extra: Default::default(),
         })
     }
 }
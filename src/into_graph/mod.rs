@@ -1,6 +1,10 @@
+//! Conversion of OBO documents into OBO graphs.
+
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 use fastobo::ast::HeaderClause;
+use fastobo::ast::HeaderFrame;
 use fastobo::ast::Ident;
 use fastobo::ast::IdentPrefix;
 use fastobo::ast::OboDoc;
@@ -13,13 +17,25 @@ use super::model::GraphDocument;
 
 mod doc;
 mod entity;
+mod frames;
 mod header;
 mod pv;
+mod xrefs;
+
+pub use self::frames::write_frames;
 
+/// The context of a document to graph conversion.
+///
+/// A context only depends on the header of the converted document, so it
+/// can be created once and reused to convert frames individually.
+#[derive(Clone, Debug)]
 pub struct Context {
+    /// The ID spaces declared in the document, used to expand prefixed IDs.
     pub idspaces: HashMap<IdentPrefix, Url>,
+    /// The IRI of the ontology, used to expand unprefixed IDs.
     pub ontology_iri: Url,
     pub current_frame: Url,
+    /// The relation shorthands, used to expand unprefixed relation IDs.
     pub shorthands: HashMap<UnprefixedIdent, Ident>,
     // pub in_annotation: bool,
     // pub class_level: HashSet<Url>,
@@ -42,8 +58,14 @@ impl Context {
     }
 }
 
-impl From<&OboDoc> for Context {
-    fn from(doc: &OboDoc) -> Self {
+impl Default for Context {
+    fn default() -> Self {
+        Self::from(&HeaderFrame::new())
+    }
+}
+
+impl From<&HeaderFrame> for Context {
+    fn from(header: &HeaderFrame) -> Self {
         // Add the ID spaces declared implicitly in the document.
         let mut idspaces = HashMap::new();
         idspaces.insert(
@@ -58,7 +80,7 @@ impl From<&OboDoc> for Context {
 
         // Add the prefixes and ID spaces from the OBO header.
         let mut ontology_iri = Url::new("http://purl.obolibrary.org/obo/TEMP").unwrap();
-        for clause in doc.header() {
+        for clause in header {
             match clause {
                 HeaderClause::Idspace(prefix, url, _) => {
                     idspaces.insert(prefix.as_ref().clone(), url.as_ref().clone());
@@ -82,8 +104,32 @@ impl From<&OboDoc> for Context {
     }
 }
 
+impl From<&OboDoc> for Context {
+    fn from(doc: &OboDoc) -> Self {
+        Self::from(doc.header())
+    }
+}
+
+/// Collect the ID spaces declared in a header as graph document prefixes.
+pub(crate) fn header_prefixes(header: &HeaderFrame) -> BTreeMap<String, String> {
+    header
+        .iter()
+        .filter_map(|clause| match clause {
+            HeaderClause::Idspace(prefix, url, _) => {
+                Some((prefix.as_str().to_string(), url.as_str().to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 /// Trait to convert an OBO syntax node into an OBO graph element.
-trait IntoGraphCtx<T> {
+///
+/// Entity frames are converted into a `Graph` containing a single node, the
+/// edges going out of that node, and the axioms declared in the frame. The
+/// header frame is converted into the `Meta` of the ontology graph.
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+pub trait IntoGraphCtx<T> {
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<T>;
 }
 
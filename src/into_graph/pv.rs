@@ -6,15 +6,23 @@ use crate::error::Result;
 use crate::model::BasicPropertyValue;
 
 impl IntoGraphCtx<BasicPropertyValue> for PropertyValue {
+    #[inline]
+    fn into_graph_ctx(self, ctx: &mut Context) -> Result<BasicPropertyValue> {
+        (&self).into_graph_ctx(ctx)
+    }
+}
+
+impl IntoGraphCtx<BasicPropertyValue> for &PropertyValue {
     fn into_graph_ctx(self, ctx: &mut Context) -> Result<BasicPropertyValue> {
         match self {
             PropertyValue::Resource(pv) => Ok(BasicPropertyValue::new(
                 ctx.expand(pv.property()),
                 ctx.expand(pv.target()),
             )),
-            PropertyValue::Literal(pv) => Ok(BasicPropertyValue::new(
+            PropertyValue::Literal(pv) => Ok(BasicPropertyValue::with_datatype(
                 ctx.expand(pv.property()),
                 pv.literal().as_str().to_string(),
+                ctx.expand(pv.datatype()),
             )),
         }
     }
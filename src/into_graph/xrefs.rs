@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use fastobo::ast::EntityFrame;
+use fastobo::ast::HeaderClause;
+use fastobo::ast::HeaderFrame;
+use fastobo::ast::Ident;
+use fastobo::ast::IdentPrefix;
+use fastobo::ast::Line;
+use fastobo::ast::TermClause;
+use fastobo::ast::TermFrame;
+use fastobo::ast::TypedefClause;
+use fastobo::ast::TypedefFrame;
+use fastobo::semantics::Identified;
+
+use super::entity::term_graph;
+use super::entity::typedef_graph;
+use super::Context;
+use super::IntoGraphCtx;
+use crate::error::Result;
+use crate::model::Graph;
+
+/// The clauses added to entity frames by the *treat-xrefs* macros of a header.
+///
+/// This mirrors `OboDoc::treat_xrefs` from `fastobo`, which modifies the
+/// document in place, by recording the clauses it would add to each frame
+/// so that a borrowed document can be converted. Only the macros adding
+/// clauses converted into edges are applied: equivalence and
+/// genus-differentia macros only add clauses missing from the graph, and
+/// instance frames are left untouched since their clauses are not converted.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TreatXrefs {
+    terms: HashMap<usize, Vec<Line<TermClause>>>,
+    typedefs: HashMap<usize, Vec<Line<TypedefClause>>>,
+}
+
+impl TreatXrefs {
+    /// Apply the macros of the header to the given entity frames.
+    pub fn new(header: &HeaderFrame, entities: &[EntityFrame]) -> Self {
+        let mut treat = Self::default();
+        for clause in header.iter() {
+            match clause {
+                HeaderClause::TreatXrefsAsIsA(prefix) => treat.add(
+                    entities,
+                    prefix,
+                    |id| TermClause::IsA(Box::new(id.clone().into())),
+                    |id| TypedefClause::IsA(Box::new(id.clone().into())),
+                ),
+                HeaderClause::TreatXrefsAsRelationship(prefix, rel) => treat.add(
+                    entities,
+                    prefix,
+                    |id| TermClause::Relationship(rel.clone(), Box::new(id.clone().into())),
+                    |id| TypedefClause::Relationship(rel.clone(), Box::new(id.clone().into())),
+                ),
+                HeaderClause::TreatXrefsAsHasSubclass(prefix) => {
+                    treat.add_subclasses(entities, prefix)
+                }
+                _ => (),
+            }
+        }
+        treat
+    }
+
+    /// Convert the entity frame at the given index, with its added clauses.
+    pub fn convert(&self, index: usize, entity: &EntityFrame, ctx: &mut Context) -> Result<Graph> {
+        match entity {
+            EntityFrame::Term(frame) => {
+                let extra = self.terms.get(&index).map(Vec::as_slice).unwrap_or(&[]);
+                term_graph(frame, extra, ctx)
+            }
+            EntityFrame::Typedef(frame) => {
+                let extra = self.typedefs.get(&index).map(Vec::as_slice).unwrap_or(&[]);
+                typedef_graph(frame, extra, ctx)
+            }
+            EntityFrame::Instance(frame) => frame.as_ref().into_graph_ctx(ctx),
+        }
+    }
+
+    /// Add a clause built from each matching xref to the frame declaring it.
+    fn add<T, D>(&mut self, entities: &[EntityFrame], prefix: &IdentPrefix, term: T, typedef: D)
+    where
+        T: Fn(&Ident) -> TermClause,
+        D: Fn(&Ident) -> TypedefClause,
+    {
+        for (i, entity) in entities.iter().enumerate() {
+            match entity {
+                EntityFrame::Term(frame) => {
+                    let new = matching(term_xrefs(frame), prefix).map(&term);
+                    push_new(frame.clauses(), &mut self.terms, i, new);
+                }
+                EntityFrame::Typedef(frame) => {
+                    let new = matching(typedef_xrefs(frame), prefix).map(&typedef);
+                    push_new(frame.clauses(), &mut self.typedefs, i, new);
+                }
+                EntityFrame::Instance(_) => (),
+            }
+        }
+    }
+
+    /// Add an `is_a` clause to the frames referenced by a matching xref.
+    ///
+    /// Like `fastobo`, only the last matching xref of each frame is used,
+    /// and the clause is only added if the xref is declared in the document.
+    fn add_subclasses(&mut self, entities: &[EntityFrame], prefix: &IdentPrefix) {
+        let mut index = HashMap::new();
+        for (i, entity) in entities.iter().enumerate() {
+            index.insert(entity.as_id(), i);
+        }
+
+        let mut seen = HashSet::new();
+        for entity in entities.iter() {
+            let xref = match entity {
+                EntityFrame::Term(frame) => matching(term_xrefs(frame), prefix).last(),
+                EntityFrame::Typedef(frame) => matching(typedef_xrefs(frame), prefix).last(),
+                EntityFrame::Instance(_) => None,
+            };
+            let superclass = entity.as_id();
+            let i = match xref.and_then(|x| index.get(x)) {
+                Some(&i) if seen.insert((superclass, i)) => i,
+                _ => continue,
+            };
+            match &entities[i] {
+                EntityFrame::Term(_) => {
+                    let clause = TermClause::IsA(Box::new(superclass.clone().into()));
+                    self.terms.entry(i).or_default().push(Line::from(clause));
+                }
+                EntityFrame::Typedef(_) => {
+                    let clause = TypedefClause::IsA(Box::new(superclass.clone().into()));
+                    self.typedefs.entry(i).or_default().push(Line::from(clause));
+                }
+                EntityFrame::Instance(_) => (),
+            }
+        }
+    }
+}
+
+/// Iterate over the xrefs of a term frame.
+fn term_xrefs(frame: &TermFrame) -> impl Iterator<Item = &Ident> {
+    frame.iter().filter_map(|line| match line.as_inner() {
+        TermClause::Xref(xref) => Some(xref.id()),
+        _ => None,
+    })
+}
+
+/// Iterate over the xrefs of a typedef frame.
+fn typedef_xrefs(frame: &TypedefFrame) -> impl Iterator<Item = &Ident> {
+    frame.iter().filter_map(|line| match line.as_inner() {
+        TypedefClause::Xref(xref) => Some(xref.id()),
+        _ => None,
+    })
+}
+
+/// Keep the identifiers with the given prefix.
+fn matching<'a, I>(ids: I, prefix: &'a IdentPrefix) -> impl Iterator<Item = &'a Ident>
+where
+    I: Iterator<Item = &'a Ident>,
+{
+    ids.filter(move |id| match id {
+        Ident::Prefixed(p) => p.prefix() == prefix.as_str(),
+        _ => false,
+    })
+}
+
+/// Record the new clauses of a frame, unless the frame already has them.
+fn push_new<C, I>(
+    clauses: &[Line<C>],
+    added: &mut HashMap<usize, Vec<Line<C>>>,
+    index: usize,
+    new: I,
+) where
+    C: PartialEq,
+    I: Iterator<Item = C>,
+{
+    for clause in new {
+        let line = Line::from(clause);
+        let lines = added.get(&index).map(Vec::as_slice).unwrap_or(&[]);
+        if !clauses.contains(&line) && !lines.contains(&line) {
+            added.entry(index).or_default().push(line);
+        }
+    }
+}
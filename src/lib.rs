@@ -11,51 +11,275 @@ extern crate thiserror;
 extern crate mashup;
 #[cfg(feature = "obo")]
 extern crate fastobo;
+#[cfg(feature = "gzip")]
+extern crate flate2;
 extern crate serde;
 extern crate serde_json;
+extern crate serde_path_to_error;
 extern crate serde_yaml;
+#[cfg(feature = "zstd")]
+extern crate zstd;
 
+pub mod algo;
 pub mod constants;
 pub mod error;
 #[cfg(feature = "obo")]
-mod from_graph;
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+pub mod from_graph;
+pub mod index;
 #[cfg(feature = "obo")]
-mod into_graph;
+#[cfg_attr(feature = "_doc", doc(cfg(feature = "obo")))]
+pub mod into_graph;
 pub mod model;
+pub mod stream;
 mod utils;
+pub mod validate;
 
 use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::BufWriter;
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+
+use self::error::Error;
 use self::error::Result;
 #[cfg(feature = "obo")]
 pub use self::from_graph::FromGraph;
 #[cfg(feature = "obo")]
+pub use self::from_graph::FromGraphCtx;
+#[cfg(feature = "obo")]
 pub use self::into_graph::IntoGraph;
+#[cfg(feature = "obo")]
+pub use self::into_graph::IntoGraphCtx;
 use self::model::GraphDocument;
+use self::model::Strict;
+use self::model::Verbose;
+use self::utils::io::format_extension;
+use self::utils::io::Compression;
 
 // ---------------------------------------------------------------------------
 
+/// Check whether a serialized graph starting with `byte` is likely in JSON format.
+///
+/// JSON graph documents are objects, while YAML ones are usually block
+/// mappings. YAML flow mappings also start with `{`, so documents detected
+/// as JSON are read again as YAML if they are not valid JSON.
+#[inline]
+fn is_json(byte: Option<u8>) -> bool {
+    byte == Some(b'{')
+}
+
+/// Check whether an error was caused by a syntax error in a JSON document.
+fn is_json_syntax_error(error: &Error) -> bool {
+    match error {
+        Error::JsonError(e) | Error::JsonPathError { error: e, .. } => e.is_syntax(),
+        _ => false,
+    }
+}
+
 /// Read an OBO graph from a string containing a JSON or YAML serialization.
+///
+/// The format is detected from the first non-whitespace character.
 #[inline]
 pub fn from_str<S: AsRef<str>>(src: S) -> Result<GraphDocument> {
-    serde_yaml::from_str::<model::GraphDocument>(src.as_ref()).map_err(From::from)
+    read_str(src.as_ref())
+}
+
+/// Read an OBO graph from a string, rejecting unknown fields.
+///
+/// See [`Strict`] for more information.
+#[inline]
+pub fn from_str_strict<S: AsRef<str>>(src: S) -> Result<GraphDocument> {
+    read_str(src.as_ref()).map(|Strict(doc)| doc)
 }
 
 /// Read an OBO graph from a `Read` implementor.
+///
+/// The format is detected from the first non-whitespace character.
 #[inline]
 pub fn from_reader<R: Read>(r: R) -> Result<GraphDocument> {
-    serde_yaml::from_reader::<R, model::GraphDocument>(r).map_err(From::from)
+    read_reader(r)
+}
+
+/// Read an OBO graph from a `Read` implementor, rejecting unknown fields.
+///
+/// See [`Strict`] for more information.
+#[inline]
+pub fn from_reader_strict<R: Read>(r: R) -> Result<GraphDocument> {
+    read_reader(r).map(|Strict(doc)| doc)
 }
 
 /// Read an OBO graph from a file on the local filesystem.
+///
+/// The format is detected from the file extension (`.json`, `.yaml` or
+/// `.yml`), or from the content of the file for other extensions. With
+/// the `gzip` or `zstd` features enabled, compressed files are detected
+/// from their extension or magic bytes and decompressed on the fly, and
+/// [`Error::UnsupportedCompression`]
+/// is returned for them when the matching feature is disabled.
 #[inline]
 pub fn from_file<P: AsRef<Path>>(path: P) -> Result<GraphDocument> {
+    read_file(path.as_ref())
+}
+
+/// Read an OBO graph from a file on the local filesystem, rejecting unknown fields.
+///
+/// See [`from_file`] and [`Strict`] for more information.
+#[inline]
+pub fn from_file_strict<P: AsRef<Path>>(path: P) -> Result<GraphDocument> {
+    read_file(path.as_ref()).map(|Strict(doc)| doc)
+}
+
+/// Read a serialized graph from a string, detecting its format.
+fn read_str<T: DeserializeOwned>(src: &str) -> Result<T> {
+    if is_json(src.bytes().find(|b| !b.is_ascii_whitespace())) {
+        // YAML is a superset of JSON, so try YAML flow mappings as well,
+        // but report the JSON error if both fail.
+        read_json_str(src).or_else(|error| {
+            if is_json_syntax_error(&error) {
+                read_yaml_str(src).map_err(|_| error)
+            } else {
+                Err(error)
+            }
+        })
+    } else {
+        read_yaml_str(src)
+    }
+}
+
+/// Read a serialized graph from a reader, detecting its format.
+fn read_reader<T: DeserializeOwned, R: Read>(r: R) -> Result<T> {
+    let mut reader = BufReader::new(r);
+    let first = loop {
+        let buffer = reader.fill_buf()?;
+        match buffer.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let byte = buffer[i];
+                reader.consume(i);
+                break Some(byte);
+            }
+            None if buffer.is_empty() => break None,
+            None => {
+                let n = buffer.len();
+                reader.consume(n);
+            }
+        }
+    };
+    if is_json(first) {
+        // buffer the document to read it again as YAML if needed
+        let mut src = String::new();
+        reader.read_to_string(&mut src)?;
+        read_str(&src)
+    } else {
+        read_yaml_reader(reader)
+    }
+}
+
+/// Read a serialized graph from a possibly compressed file.
+fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let format = format_extension(path);
+    let mut reader = File::open(path).map(BufReader::new)?;
+    let compression = match Compression::from_reader(&mut reader)? {
+        Some(compression) => Some(compression),
+        None => Compression::from_path(path),
+    };
+    match compression {
+        Some(c) if !c.is_supported() => Err(Error::UnsupportedCompression(c.feature())),
+        #[cfg(feature = "gzip")]
+        Some(Compression::Gzip) => {
+            let decoder = flate2::bufread::MultiGzDecoder::new(reader);
+            read_format_reader(format, decoder)
+        }
+        #[cfg(feature = "zstd")]
+        Some(Compression::Zstd) => {
+            let decoder = zstd::Decoder::with_buffer(reader)?;
+            read_format_reader(format, decoder)
+        }
+        _ => read_format_reader(format, reader),
+    }
+}
+
+/// Read a serialized graph in the format of the given file extension.
+fn read_format_reader<T: DeserializeOwned, R: Read>(format: Option<&str>, r: R) -> Result<T> {
+    match format {
+        Some("json") => read_json_reader(BufReader::new(r)),
+        Some("yaml") | Some("yml") => read_yaml_reader(r),
+        _ => read_reader(r),
+    }
+}
+
+/// Read a graph serialized in JSON from a string.
+fn read_json_str<T: DeserializeOwned>(src: &str) -> Result<T> {
+    let mut de = serde_json::Deserializer::from_str(src);
+    let doc = serde_path_to_error::deserialize(&mut de)?;
+    de.end()?;
+    Ok(doc)
+}
+
+/// Read a graph serialized in JSON from a reader.
+fn read_json_reader<T: DeserializeOwned, R: Read>(r: R) -> Result<T> {
+    let mut de = serde_json::Deserializer::from_reader(r);
+    let doc = serde_path_to_error::deserialize(&mut de)?;
+    de.end()?;
+    Ok(doc)
+}
+
+/// Read a graph serialized in YAML from a string.
+fn read_yaml_str<T: DeserializeOwned>(src: &str) -> Result<T> {
+    let de = serde_yaml::Deserializer::from_str(src);
+    serde_path_to_error::deserialize(de).map_err(From::from)
+}
+
+/// Read a graph serialized in YAML from a reader.
+fn read_yaml_reader<T: DeserializeOwned, R: Read>(r: R) -> Result<T> {
+    let de = serde_yaml::Deserializer::from_reader(r);
+    serde_path_to_error::deserialize(de).map_err(From::from)
+}
+
+/// Read an OBO graph from a string containing a JSON serialization.
+#[inline]
+pub fn from_json_str<S: AsRef<str>>(src: S) -> Result<GraphDocument> {
+    read_json_str(src.as_ref())
+}
+
+/// Read an OBO graph serialized in JSON from a `Read` implementor.
+#[inline]
+pub fn from_json_reader<R: Read>(r: R) -> Result<GraphDocument> {
+    read_json_reader(r)
+}
+
+/// Read an OBO graph serialized in JSON from a file on the local filesystem.
+#[inline]
+pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<GraphDocument> {
     File::open(path)
         .map_err(From::from)
-        .and_then(|r| serde_yaml::from_reader(r).map_err(From::from))
+        .and_then(|r| from_json_reader(BufReader::new(r)))
+}
+
+/// Read an OBO graph from a string containing a YAML serialization.
+#[inline]
+pub fn from_yaml_str<S: AsRef<str>>(src: S) -> Result<GraphDocument> {
+    read_yaml_str(src.as_ref())
+}
+
+/// Read an OBO graph serialized in YAML from a `Read` implementor.
+#[inline]
+pub fn from_yaml_reader<R: Read>(r: R) -> Result<GraphDocument> {
+    read_yaml_reader(r)
+}
+
+/// Read an OBO graph serialized in YAML from a file on the local filesystem.
+#[inline]
+pub fn from_yaml_file<P: AsRef<Path>>(path: P) -> Result<GraphDocument> {
+    File::open(path)
+        .map_err(From::from)
+        .and_then(from_yaml_reader)
 }
 
 // ---------------------------------------------------------------------------
@@ -66,16 +290,125 @@ pub fn to_string(g: &GraphDocument) -> Result<String> {
     serde_json::to_string(g).map_err(From::from)
 }
 
+/// Write an OBO graph to a string, with all its empty fields.
+///
+/// See [`Verbose`] for more information.
+#[inline]
+pub fn to_string_verbose(g: &GraphDocument) -> Result<String> {
+    serde_json::to_string(&Verbose(g)).map_err(From::from)
+}
+
 /// Write an OBO graph to a `Write` implementor.
 #[inline]
 pub fn to_writer<W: Write>(w: W, g: &GraphDocument) -> Result<()> {
     serde_json::to_writer(w, g).map_err(From::from)
 }
 
+/// Write an OBO graph to a `Write` implementor, with all its empty fields.
+///
+/// See [`Verbose`] for more information.
+#[inline]
+pub fn to_writer_verbose<W: Write>(w: W, g: &GraphDocument) -> Result<()> {
+    serde_json::to_writer(w, &Verbose(g)).map_err(From::from)
+}
+
 /// Write an OBO graph to a file on the local filesystem.
+///
+/// The graph is written in YAML if the path has a `.yaml` or `.yml`
+/// extension, and in compact JSON otherwise. With the `gzip` or `zstd`
+/// features enabled, paths with a `.gz` or `.zst` extension are compressed
+/// on the fly (e.g. `go.json.gz`), otherwise
+/// [`Error::UnsupportedCompression`]
+/// is returned before the file is created.
 #[inline]
 pub fn to_file<P: AsRef<Path>>(path: P, g: &GraphDocument) -> Result<()> {
-    File::create(path)
-        .map_err(From::from)
-        .and_then(|w| to_writer(w, g))
+    write_file(path.as_ref(), g)
+}
+
+/// Write an OBO graph to a file on the local filesystem, with all its empty fields.
+///
+/// See [`to_file`] and [`Verbose`] for more information.
+#[inline]
+pub fn to_file_verbose<P: AsRef<Path>>(path: P, g: &GraphDocument) -> Result<()> {
+    write_file(path.as_ref(), &Verbose(g))
+}
+
+/// Write a serialized graph to a possibly compressed file.
+fn write_file<T: Serialize>(path: &Path, g: &T) -> Result<()> {
+    let format = format_extension(path);
+    let compression = Compression::from_path(path);
+    if let Some(c) = compression.filter(|c| !c.is_supported()) {
+        return Err(Error::UnsupportedCompression(c.feature()));
+    }
+
+    let w = File::create(path).map(BufWriter::new)?;
+    let mut w = match compression {
+        #[cfg(feature = "gzip")]
+        Some(Compression::Gzip) => {
+            let mut encoder = flate2::write::GzEncoder::new(w, flate2::Compression::default());
+            write_format_writer(format, &mut encoder, g)?;
+            encoder.finish()?
+        }
+        #[cfg(feature = "zstd")]
+        Some(Compression::Zstd) => {
+            let mut encoder = zstd::Encoder::new(w, 0)?;
+            write_format_writer(format, &mut encoder, g)?;
+            encoder.finish()?
+        }
+        _ => {
+            let mut w = w;
+            write_format_writer(format, &mut w, g)?;
+            w
+        }
+    };
+    w.flush().map_err(From::from)
+}
+
+/// Write a serialized graph in the format of the given file extension.
+fn write_format_writer<W: Write, T: Serialize>(format: Option<&str>, w: W, g: &T) -> Result<()> {
+    match format {
+        Some("yaml") | Some("yml") => serde_yaml::to_writer(w, g).map_err(From::from),
+        _ => serde_json::to_writer(w, g).map_err(From::from),
+    }
+}
+
+/// Write an OBO graph to a pretty-printed JSON string.
+#[inline]
+pub fn to_string_pretty(g: &GraphDocument) -> Result<String> {
+    serde_json::to_string_pretty(g).map_err(From::from)
+}
+
+/// Write an OBO graph as pretty-printed JSON to a `Write` implementor.
+#[inline]
+pub fn to_writer_pretty<W: Write>(w: W, g: &GraphDocument) -> Result<()> {
+    serde_json::to_writer_pretty(w, g).map_err(From::from)
+}
+
+/// Write an OBO graph as JSON indented with `indent` to a `Write` implementor.
+///
+/// # Example
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// let doc = fastobo_graphs::from_str(r#"{"graphs": [{"id": "test"}]}"#).unwrap();
+/// let mut json = Vec::new();
+/// fastobo_graphs::to_writer_indent(&mut json, &doc, "\t").unwrap();
+/// assert_eq!(json, b"{\n\t\"graphs\": [\n\t\t{\n\t\t\t\"id\": \"test\"\n\t\t}\n\t]\n}");
+/// ```
+#[inline]
+pub fn to_writer_indent<W: Write>(w: W, g: &GraphDocument, indent: &str) -> Result<()> {
+    let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(w, formatter);
+    g.serialize(&mut serializer).map_err(From::from)
+}
+
+/// Write an OBO graph to a YAML string.
+#[inline]
+pub fn to_yaml_string(g: &GraphDocument) -> Result<String> {
+    serde_yaml::to_string(g).map_err(From::from)
+}
+
+/// Write an OBO graph as YAML to a `Write` implementor.
+#[inline]
+pub fn to_yaml_writer<W: Write>(w: W, g: &GraphDocument) -> Result<()> {
+    serde_yaml::to_writer(w, g).map_err(From::from)
 }
@@ -4,27 +4,119 @@
 //! The [OBO Graphs JSON schema](https://github.com/geneontology/obographs/tree/master/schema)
 //! developed in the Gene Ontology project.
 
+use std::collections::BTreeMap;
 use std::iter::FromIterator;
 
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
+use serde_json::Value;
 
-use crate::utils::serde::nullable_vector;
-use crate::utils::serde::optional_vector;
+use crate::utils::serde::impl_element;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+/// An element of the data model which can be serialized verbosely.
+///
+/// This is implemented by all elements of the data model, and by the
+/// containers of elements, to support the [`Verbose`] wrapper.
+pub trait SerializeElement {
+    /// Serialize the element, with its empty fields if `verbose` is `true`.
+    fn serialize_element<S: Serializer>(
+        &self,
+        serializer: S,
+        verbose: bool,
+    ) -> Result<S::Ok, S::Error>;
+}
+
+/// An element of the data model which can be deserialized strictly.
+///
+/// This is implemented by all elements of the data model, and by the
+/// containers of elements, to support the [`Strict`] wrapper.
+pub trait DeserializeElement<'de>: Sized {
+    /// Deserialize the element, rejecting unknown fields if `strict` is `true`.
+    fn deserialize_element<D: Deserializer<'de>>(
+        deserializer: D,
+        strict: bool,
+    ) -> Result<Self, D::Error>;
+}
+
+/// A wrapper to serialize an element with all its fields.
+///
+/// By default, `None` values, empty vectors and `false` flags are omitted
+/// from the serialized output, like the reference `obographs` exporter does.
+/// Wrapping an element in `Verbose` serializes these fields as well:
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// # use fastobo_graphs::model::*;
+/// let edge = Edge {
+///     sub: String::from("A"),
+///     pred: String::from("is_a"),
+///     obj: String::from("B"),
+///     meta: None,
+///     extra: Default::default(),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&edge).unwrap(),
+///     r#"{"sub":"A","pred":"is_a","obj":"B"}"#,
+/// );
+/// assert_eq!(
+///     serde_json::to_string(&Verbose(&edge)).unwrap(),
+///     r#"{"sub":"A","pred":"is_a","obj":"B","meta":null}"#,
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Verbose<T>(pub T);
+
+impl<T: SerializeElement> Serialize for Verbose<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_element(serializer, true)
+    }
+}
+
+/// A wrapper to deserialize an element rejecting unknown fields.
+///
+/// By default, fields not declared in the data model are kept in the
+/// `extra` map of each element, so that they are preserved when the element
+/// is serialized again. Wrapping the element type in `Strict` makes the
+/// deserialization fail on unknown fields instead:
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// # use fastobo_graphs::model::*;
+/// let json = r#"{"sub": "A", "pred": "is_a", "obj": "B", "vendor": 1}"#;
+/// let edge: Edge = serde_json::from_str(json).unwrap();
+/// assert_eq!(edge.extra["vendor"], 1);
+/// assert!(serde_json::from_str::<Strict<Edge>>(json).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Strict<T>(pub T);
+
+impl<'de, T: DeserializeElement<'de>> Deserialize<'de> for Strict<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_element(deserializer, true).map(Strict)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GraphDocument {
-    #[serde(default, deserialize_with = "optional_vector")]
     pub graphs: Vec<Graph>,
-    #[serde(default)]
     pub meta: Option<Box<Meta>>,
+    pub prefixes: BTreeMap<String, String>,
+    pub extra: BTreeMap<String, Value>,
 }
 
+impl_element!(GraphDocument {
+    graphs: "graphs",
+    meta: "meta",
+    prefixes: "prefixes",
+});
+
 impl From<Graph> for GraphDocument {
     fn from(graph: Graph) -> Self {
         Self {
             graphs: vec![graph],
             meta: None,
+            prefixes: BTreeMap::new(),
+            extra: BTreeMap::new(),
         }
     }
 }
@@ -37,46 +129,38 @@ impl FromIterator<Graph> for GraphDocument {
         Self {
             graphs: iter.into_iter().collect(),
             meta: None,
+            prefixes: BTreeMap::new(),
+            extra: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Graph {
-    #[serde(default, deserialize_with = "optional_vector")]
     pub nodes: Vec<Node>,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub edges: Vec<Edge>,
     pub id: String,
-    #[serde(rename = "lbl")]
     pub label: Option<String>,
     pub meta: Option<Box<Meta>>,
-    #[serde(
-        default,
-        rename = "equivalentNodesSets",
-        deserialize_with = "optional_vector"
-    )]
     pub equivalent_nodes_sets: Vec<EquivalentNodesSet>,
-    #[serde(
-        default,
-        rename = "logicalDefinitionAxioms",
-        deserialize_with = "optional_vector"
-    )]
     pub logical_definition_axioms: Vec<LogicalDefinitionAxiom>,
-    #[serde(
-        default,
-        rename = "domainRangeAxioms",
-        deserialize_with = "optional_vector"
-    )]
     pub domain_range_axioms: Vec<DomainRangeAxiom>,
-    #[serde(
-        default,
-        rename = "propertyChainAxioms",
-        deserialize_with = "optional_vector"
-    )]
     pub property_chain_axioms: Vec<PropertyChainAxiom>,
+    pub extra: BTreeMap<String, Value>,
 }
 
+impl_element!(Graph {
+    nodes: "nodes",
+    edges: "edges",
+    id: "id",
+    label: "lbl",
+    meta: "meta",
+    equivalent_nodes_sets: "equivalentNodesSets",
+    logical_definition_axioms: "logicalDefinitionAxioms",
+    domain_range_axioms: "domainRangeAxioms",
+    property_chain_axioms: "propertyChainAxioms",
+});
+
 impl Graph {
     pub fn extend(&mut self, other: Self) {
         self.nodes.extend(other.nodes);
@@ -91,48 +175,65 @@ impl Graph {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Node {
     pub id: String,
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "type")]
-    pub ty: Option<NodeType>, // FIXME: Use `CLASS` as default instead?
-    #[serde(rename = "lbl")]
+    pub ty: Option<NodeType>,
     pub label: Option<String>,
+    pub property_type: Option<PropertyType>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+impl_element!(Node {
+    id: "id",
+    meta: "meta",
+    ty: "type",
+    label: "lbl",
+    property_type: "propertyType",
+});
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct Meta {
     pub definition: Option<Box<DefinitionPropertyValue>>,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub comments: Vec<String>,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub subsets: Vec<String>,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub xrefs: Vec<XrefPropertyValue>,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub synonyms: Vec<SynonymPropertyValue>,
-    #[serde(
-        rename = "basicPropertyValues",
-        default,
-        deserialize_with = "optional_vector"
-    )]
     pub basic_property_values: Vec<BasicPropertyValue>,
     pub version: Option<String>,
-    #[serde(default)]
     pub deprecated: bool,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(Meta {
+    definition: "definition",
+    comments: "comments",
+    subsets: "subsets",
+    xrefs: "xrefs",
+    synonyms: "synonyms",
+    basic_property_values: "basicPropertyValues",
+    version: "version",
+    deprecated: "deprecated",
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DefinitionPropertyValue {
     pub pred: Option<String>,
     pub val: String,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub xrefs: Vec<String>,
     pub meta: Option<Box<Meta>>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(DefinitionPropertyValue {
+    pred: "pred",
+    val: "val",
+    xrefs: "xrefs",
+    meta: "meta",
+});
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum NodeType {
     Class,
@@ -140,118 +241,166 @@ pub enum NodeType {
     Property,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PropertyType {
+    Annotation,
+    Object,
+    Data,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Edge {
     pub sub: String,
     pub pred: String,
     pub obj: String,
     pub meta: Option<Box<Meta>>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+impl_element!(Edge {
+    sub: "sub",
+    pred: "pred",
+    obj: "obj",
+    meta: "meta",
+});
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct EquivalentNodesSet {
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "representativeNodeId")]
     pub representative_node_id: Option<String>,
-    #[serde(rename = "nodeIds", default, deserialize_with = "optional_vector")]
     pub node_ids: Vec<String>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+impl_element!(EquivalentNodesSet {
+    meta: "meta",
+    representative_node_id: "representativeNodeId",
+    node_ids: "nodeIds",
+});
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct LogicalDefinitionAxiom {
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "definedClassId")]
     pub defined_class_id: String,
-    #[serde(rename = "genusIds", deserialize_with = "optional_vector")]
     pub genus_ids: Vec<String>,
-    #[serde(default, deserialize_with = "nullable_vector")]
     pub restrictions: Vec<ExistentialRestrictionExpression>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+impl_element!(LogicalDefinitionAxiom {
+    meta: "meta",
+    defined_class_id: "definedClassId",
+    genus_ids: "genusIds",
+    restrictions: "restrictions" with NullableSeed,
+});
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct ExistentialRestrictionExpression {
-    #[serde(rename = "propertyId")]
     pub property_id: String,
-    #[serde(rename = "fillerId")]
     pub filler_id: String,
+    pub extra: BTreeMap<String, Value>,
 }
 
+impl_element!(ExistentialRestrictionExpression {
+    property_id: "propertyId",
+    filler_id: "fillerId",
+});
+
 impl ExistentialRestrictionExpression {
     pub fn new(property_id: String, filler_id: String) -> Self {
         Self {
             property_id,
             filler_id,
+            extra: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DomainRangeAxiom {
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "predicateId")]
     pub predicate_id: String,
-    #[serde(
-        rename = "domainClassIds",
-        default,
-        deserialize_with = "optional_vector"
-    )]
     pub domain_class_ids: Vec<String>,
-    #[serde(
-        rename = "rangeClassIds",
-        default,
-        deserialize_with = "optional_vector"
-    )]
     pub range_class_ids: Vec<String>,
-    #[serde(
-        rename = "allValuesFromEdges",
-        default,
-        deserialize_with = "optional_vector"
-    )]
     pub all_values_from_edges: Vec<Edge>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(DomainRangeAxiom {
+    meta: "meta",
+    predicate_id: "predicateId",
+    domain_class_ids: "domainClassIds",
+    range_class_ids: "rangeClassIds",
+    all_values_from_edges: "allValuesFromEdges",
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PropertyChainAxiom {
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "predicateId")]
     pub predicate_id: String,
-    #[serde(
-        rename = "chainPredicateIds",
-        default,
-        deserialize_with = "optional_vector"
-    )]
     pub chain_predicate_ids: Vec<String>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(PropertyChainAxiom {
+    meta: "meta",
+    predicate_id: "predicateId",
+    chain_predicate_ids: "chainPredicateIds",
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct XrefPropertyValue {
     pub pred: Option<String>,
     pub val: String,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub xrefs: Vec<String>,
     pub meta: Option<Box<Meta>>,
-    #[serde(rename = "lbl")]
     pub label: Option<String>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(XrefPropertyValue {
+    pred: "pred",
+    val: "val",
+    xrefs: "xrefs",
+    meta: "meta",
+    label: "lbl",
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SynonymPropertyValue {
     pub pred: String,
     pub val: String,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub xrefs: Vec<String>,
     pub meta: Option<Box<Meta>>,
+    pub extra: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+impl_element!(SynonymPropertyValue {
+    pred: "pred",
+    val: "val",
+    xrefs: "xrefs",
+    meta: "meta",
+});
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BasicPropertyValue {
     pub pred: String,
     pub val: String,
-    #[serde(default, deserialize_with = "optional_vector")]
     pub xrefs: Vec<String>,
     pub meta: Option<Box<Meta>>,
+    pub val_type: Option<String>,
+    pub extra: BTreeMap<String, Value>,
 }
 
+impl_element!(BasicPropertyValue {
+    pred: "pred",
+    val: "val",
+    xrefs: "xrefs",
+    meta: "meta",
+    val_type: "valType",
+});
+
 impl BasicPropertyValue {
     pub fn new(predicate: String, value: String) -> Self {
         Self {
@@ -259,6 +408,15 @@ impl BasicPropertyValue {
             val: value,
             xrefs: Vec::new(),
             meta: None,
+            val_type: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_datatype(predicate: String, value: String, datatype: String) -> Self {
+        Self {
+            val_type: Some(datatype),
+            ..Self::new(predicate, value)
         }
     }
 }
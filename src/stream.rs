@@ -0,0 +1,634 @@
+//! Incremental serialization and deserialization of OBO graphs.
+//!
+//! The functions of the crate root process a whole `GraphDocument` at
+//! once, which requires the complete graph to be kept in memory. The types
+//! of this module instead read or write a graph element by element.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use serde::de::DeserializeOwned;
+use serde::de::Error as _;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::model::DomainRangeAxiom;
+use crate::model::Edge;
+use crate::model::EquivalentNodesSet;
+use crate::model::Graph;
+use crate::model::LogicalDefinitionAxiom;
+use crate::model::Meta;
+use crate::model::Node;
+use crate::model::PropertyChainAxiom;
+use crate::model::SerializeElement;
+use crate::model::Verbose;
+
+/// A writer serializing a single-graph `GraphDocument` incrementally to JSON.
+///
+/// Nodes are written to the underlying writer as soon as they are received.
+/// Since the JSON layout requires all edges to follow all nodes, edges are
+/// serialized to a *spill* buffer, and copied to the underlying writer when
+/// the graph is finished. Axioms are small and are kept in memory.
+///
+/// By default, the spill buffer is kept in memory, but any seekable stream
+/// can be used instead, such as a temporary file, using
+/// [`GraphWriter::with_spill`].
+///
+/// The output is identical to the serialization of the equivalent
+/// `GraphDocument` with [`to_writer`](../fn.to_writer.html), or of the
+/// equivalent [`Verbose`] document in verbose mode.
+#[derive(Debug)]
+pub struct GraphWriter<W: Write, S: Read + Write + Seek = Cursor<Vec<u8>>> {
+    writer: W,
+    spill: S,
+    verbose: bool,
+    fields: usize,
+    nodes: usize,
+    edges: usize,
+    id: String,
+    label: Option<String>,
+    meta: Option<Box<Meta>>,
+    equivalent_nodes_sets: Vec<EquivalentNodesSet>,
+    logical_definition_axioms: Vec<LogicalDefinitionAxiom>,
+    domain_range_axioms: Vec<DomainRangeAxiom>,
+    property_chain_axioms: Vec<PropertyChainAxiom>,
+    extra: BTreeMap<String, Value>,
+    prefixes: BTreeMap<String, String>,
+}
+
+impl<W: Write> GraphWriter<W> {
+    /// Create a new writer for a graph, spilling edges to memory.
+    pub fn new(writer: W, id: String, meta: Option<Box<Meta>>) -> Result<Self> {
+        Self::with_spill(writer, Cursor::new(Vec::new()), id, meta)
+    }
+}
+
+impl<W: Write, S: Read + Write + Seek> GraphWriter<W, S> {
+    /// Create a new writer for a graph, spilling edges to the given stream.
+    ///
+    /// The spill stream is expected to be empty, and is rewound before its
+    /// content is copied to the writer.
+    pub fn with_spill(
+        mut writer: W,
+        spill: S,
+        id: String,
+        meta: Option<Box<Meta>>,
+    ) -> Result<Self> {
+        writer.write_all(br#"{"graphs":[{"#)?;
+        Ok(Self {
+            writer,
+            spill,
+            verbose: false,
+            fields: 0,
+            nodes: 0,
+            edges: 0,
+            id,
+            label: None,
+            meta,
+            equivalent_nodes_sets: Vec::new(),
+            logical_definition_axioms: Vec::new(),
+            domain_range_axioms: Vec::new(),
+            property_chain_axioms: Vec::new(),
+            extra: BTreeMap::new(),
+            prefixes: BTreeMap::new(),
+        })
+    }
+
+    /// Set the label of the graph.
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+
+    /// Set the unknown fields of the graph.
+    pub fn set_extra(&mut self, extra: BTreeMap<String, Value>) {
+        self.extra = extra;
+    }
+
+    /// Set the prefixes of the graph document.
+    pub fn set_prefixes(&mut self, prefixes: BTreeMap<String, String>) {
+        self.prefixes = prefixes;
+    }
+
+    /// Set whether to serialize empty fields, as [`Verbose`] does.
+    ///
+    /// This should be called before any element is written.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Write a single node.
+    pub fn write_node(&mut self, node: &Node) -> Result<()> {
+        if self.nodes == 0 {
+            self.write_key("nodes")?;
+            self.writer.write_all(b"[")?;
+        } else {
+            self.writer.write_all(b",")?;
+        }
+        if self.verbose {
+            serde_json::to_writer(&mut self.writer, &Verbose(node))?;
+        } else {
+            serde_json::to_writer(&mut self.writer, node)?;
+        }
+        self.nodes += 1;
+        Ok(())
+    }
+
+    /// Write a single edge.
+    pub fn write_edge(&mut self, edge: &Edge) -> Result<()> {
+        if self.edges > 0 {
+            self.spill.write_all(b",")?;
+        }
+        if self.verbose {
+            serde_json::to_writer(&mut self.spill, &Verbose(edge))?;
+        } else {
+            serde_json::to_writer(&mut self.spill, edge)?;
+        }
+        self.edges += 1;
+        Ok(())
+    }
+
+    /// Write the nodes and edges of a graph, and buffer its axioms.
+    ///
+    /// The `id`, `lbl` and `meta` of the graph are ignored.
+    pub fn write_graph(&mut self, graph: Graph) -> Result<()> {
+        for node in graph.nodes.iter() {
+            self.write_node(node)?;
+        }
+        for edge in graph.edges.iter() {
+            self.write_edge(edge)?;
+        }
+        self.equivalent_nodes_sets
+            .extend(graph.equivalent_nodes_sets);
+        self.logical_definition_axioms
+            .extend(graph.logical_definition_axioms);
+        self.domain_range_axioms.extend(graph.domain_range_axioms);
+        self.property_chain_axioms
+            .extend(graph.property_chain_axioms);
+        Ok(())
+    }
+
+    /// Write the key of the next field of the graph object.
+    fn write_key(&mut self, key: &str) -> Result<()> {
+        if self.fields > 0 {
+            self.writer.write_all(b",")?;
+        }
+        serde_json::to_writer(&mut self.writer, key)?;
+        self.writer.write_all(b":")?;
+        self.fields += 1;
+        Ok(())
+    }
+
+    /// Write a field of the graph object, unless it should be skipped.
+    fn write_field<T>(&mut self, key: &str, value: &T, empty: bool) -> Result<()>
+    where
+        T: Serialize + SerializeElement,
+    {
+        if self.verbose {
+            self.write_key(key)?;
+            serde_json::to_writer(&mut self.writer, &Verbose(value))?;
+        } else if !empty {
+            self.write_key(key)?;
+            serde_json::to_writer(&mut self.writer, value)?;
+        }
+        Ok(())
+    }
+
+    /// Finish writing the graph document and return the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        // close the nodes
+        if self.nodes > 0 {
+            self.writer.write_all(b"]")?;
+        } else if self.verbose {
+            self.write_key("nodes")?;
+            self.writer.write_all(b"[]")?;
+        }
+
+        // copy the spilled edges
+        if self.edges > 0 || self.verbose {
+            self.write_key("edges")?;
+            self.writer.write_all(b"[")?;
+            self.spill.seek(SeekFrom::Start(0))?;
+            std::io::copy(&mut self.spill, &mut self.writer)?;
+            self.writer.write_all(b"]")?;
+        }
+
+        // write the graph attributes and buffered axioms
+        let id = std::mem::take(&mut self.id);
+        self.write_field("id", &id, false)?;
+        let label = self.label.take();
+        self.write_field("lbl", &label, label.is_none())?;
+        let meta = self.meta.take();
+        self.write_field("meta", &meta, meta.is_none())?;
+        let sets = std::mem::take(&mut self.equivalent_nodes_sets);
+        self.write_field("equivalentNodesSets", &sets, sets.is_empty())?;
+        let lda = std::mem::take(&mut self.logical_definition_axioms);
+        self.write_field("logicalDefinitionAxioms", &lda, lda.is_empty())?;
+        let dra = std::mem::take(&mut self.domain_range_axioms);
+        self.write_field("domainRangeAxioms", &dra, dra.is_empty())?;
+        let pca = std::mem::take(&mut self.property_chain_axioms);
+        self.write_field("propertyChainAxioms", &pca, pca.is_empty())?;
+        for (key, value) in std::mem::take(&mut self.extra) {
+            self.write_key(&key)?;
+            serde_json::to_writer(&mut self.writer, &value)?;
+        }
+
+        // close the graph and write the document attributes
+        self.writer.write_all(b"}]")?;
+        if self.verbose {
+            self.writer.write_all(br#","meta":null"#)?;
+        }
+        if self.verbose || !self.prefixes.is_empty() {
+            self.writer.write_all(br#","prefixes":"#)?;
+            serde_json::to_writer(&mut self.writer, &self.prefixes)?;
+        }
+        self.writer.write_all(b"}")?;
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// An element of a graph document, as read by a [`GraphReader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The start of a new graph.
+    StartGraph,
+    /// The identifier of the current graph.
+    Id(String),
+    /// The label of the current graph.
+    Label(String),
+    /// The metadata of the current graph.
+    Meta(Box<Meta>),
+    /// A node of the current graph.
+    Node(Node),
+    /// An edge of the current graph.
+    Edge(Edge),
+    /// An equivalent nodes set of the current graph.
+    EquivalentNodesSet(EquivalentNodesSet),
+    /// A logical definition axiom of the current graph.
+    LogicalDefinitionAxiom(LogicalDefinitionAxiom),
+    /// A domain and range axiom of the current graph.
+    DomainRangeAxiom(DomainRangeAxiom),
+    /// A property chain axiom of the current graph.
+    PropertyChainAxiom(PropertyChainAxiom),
+    /// An unknown field of the current graph.
+    Extra(String, Value),
+    /// The end of the current graph.
+    EndGraph,
+    /// The metadata of the graph document.
+    DocumentMeta(Box<Meta>),
+    /// The prefixes of the graph document.
+    DocumentPrefixes(BTreeMap<String, String>),
+    /// An unknown field of the graph document.
+    DocumentExtra(String, Value),
+}
+
+/// The arrays of a graph which are read item by item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Items {
+    Nodes,
+    Edges,
+    EquivalentNodesSets,
+    LogicalDefinitionAxioms,
+    DomainRangeAxioms,
+    PropertyChainAxioms,
+}
+
+/// The position of a reader in the document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Start,
+    Document { first: bool },
+    Graphs { first: bool },
+    Graph { first: bool },
+    Items { first: bool, items: Items },
+    Done,
+}
+
+/// A pull reader yielding the elements of a JSON graph document one at a time.
+///
+/// Only a single element is deserialized in memory at any time, so that
+/// very large graph documents can be processed with a bounded memory usage:
+/// ```rust
+/// # extern crate fastobo_graphs;
+/// use fastobo_graphs::stream::GraphReader;
+///
+/// let json = r#"{"graphs": [{"id": "test", "nodes": [{"id": "A"}, {"id": "B"}]}]}"#;
+/// let ids = GraphReader::new(json.as_bytes())
+///     .nodes()
+///     .map(|node| node.map(|n| n.id))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(ids, vec!["A", "B"]);
+/// ```
+///
+/// Unlike the functions of the crate root, only the JSON serialization is
+/// supported.
+#[derive(Debug)]
+pub struct GraphReader<R: BufRead> {
+    reader: R,
+    state: State,
+    buffer: Vec<u8>,
+}
+
+impl<R: BufRead> GraphReader<R> {
+    /// Create a new reader for the given JSON stream.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            state: State::Start,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Extract the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Iterate over the nodes of all the graphs in the document.
+    pub fn nodes(self) -> impl Iterator<Item = Result<Node>> {
+        self.filter_map(|event| match event {
+            Ok(Event::Node(node)) => Some(Ok(node)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    /// Iterate over the edges of all the graphs in the document.
+    pub fn edges(self) -> impl Iterator<Item = Result<Edge>> {
+        self.filter_map(|event| match event {
+            Ok(Event::Edge(edge)) => Some(Ok(edge)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    // --- Scanner -----------------------------------------------------------
+
+    fn syntax_error(msg: &str) -> Error {
+        Error::from(serde_json::Error::custom(msg))
+    }
+
+    /// Peek the next byte, or `None` at the end of the stream.
+    fn peek(&mut self) -> Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    /// Consume the next byte, failing at the end of the stream.
+    fn bump(&mut self) -> Result<u8> {
+        let byte = self
+            .peek()?
+            .ok_or_else(|| Self::syntax_error("unexpected end of stream"))?;
+        self.reader.consume(1);
+        Ok(byte)
+    }
+
+    /// Skip whitespace and peek the next byte.
+    fn peek_token(&mut self) -> Result<Option<u8>> {
+        while let Some(byte) = self.peek()? {
+            if byte.is_ascii_whitespace() {
+                self.reader.consume(1);
+            } else {
+                return Ok(Some(byte));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Skip whitespace and consume the given byte.
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        match self.peek_token()? {
+            Some(byte) if byte == expected => {
+                self.reader.consume(1);
+                Ok(())
+            }
+            _ => Err(Self::syntax_error(&format!(
+                "expected `{}`",
+                expected as char
+            ))),
+        }
+    }
+
+    /// Advance to the next item of a collection ending with `end`.
+    ///
+    /// Returns `false` once the end of the collection has been consumed.
+    fn next_item(&mut self, first: bool, end: u8) -> Result<bool> {
+        if self.peek_token()? == Some(end) {
+            self.reader.consume(1);
+            Ok(false)
+        } else {
+            if !first {
+                self.expect(b',')?;
+            }
+            Ok(true)
+        }
+    }
+
+    /// Read an object key and the following colon.
+    fn read_key(&mut self) -> Result<String> {
+        self.expect(b'"')?;
+        self.buffer.clear();
+        self.buffer.push(b'"');
+        self.read_string_tail()?;
+        self.expect(b':')?;
+        serde_json::from_slice(&self.buffer).map_err(Error::from)
+    }
+
+    /// Copy the rest of a string into the buffer, after the opening quote.
+    fn read_string_tail(&mut self) -> Result<()> {
+        loop {
+            let byte = self.bump()?;
+            self.buffer.push(byte);
+            match byte {
+                b'"' => return Ok(()),
+                b'\\' => {
+                    let escaped = self.bump()?;
+                    self.buffer.push(escaped);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Copy the next JSON value into the buffer.
+    fn read_value(&mut self) -> Result<()> {
+        self.buffer.clear();
+        self.peek_token()?;
+        let mut depth = 0usize;
+        loop {
+            match self.peek()? {
+                Some(b'"') => {
+                    self.reader.consume(1);
+                    self.buffer.push(b'"');
+                    self.read_string_tail()?;
+                }
+                Some(byte @ b'{') | Some(byte @ b'[') => {
+                    self.buffer.push(byte);
+                    self.reader.consume(1);
+                    depth += 1;
+                }
+                Some(byte @ b'}') | Some(byte @ b']') => {
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                    self.buffer.push(byte);
+                    self.reader.consume(1);
+                    depth -= 1;
+                }
+                Some(b',') if depth == 0 => return Ok(()),
+                Some(byte) => {
+                    self.buffer.push(byte);
+                    self.reader.consume(1);
+                }
+                None if depth == 0 => return Ok(()),
+                None => return Err(Self::syntax_error("unexpected end of stream")),
+            }
+            if depth == 0 && matches!(self.buffer.last(), Some(b'}') | Some(b']') | Some(b'"')) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Read and deserialize the next JSON value.
+    fn parse_value<T: DeserializeOwned>(&mut self) -> Result<T> {
+        self.read_value()?;
+        serde_json::from_slice(&self.buffer).map_err(Error::from)
+    }
+
+    // --- State machine -----------------------------------------------------
+
+    fn advance(&mut self) -> Result<Option<Event>> {
+        loop {
+            match self.state {
+                State::Start => {
+                    self.expect(b'{')?;
+                    self.state = State::Document { first: true };
+                }
+                State::Done => return Ok(None),
+                State::Document { first } => {
+                    if !self.next_item(first, b'}')? {
+                        self.state = State::Done;
+                        continue;
+                    }
+                    self.state = State::Document { first: false };
+                    let key = self.read_key()?;
+                    match key.as_str() {
+                        "graphs" if self.peek_token()? == Some(b'[') => {
+                            self.reader.consume(1);
+                            self.state = State::Graphs { first: true };
+                        }
+                        "graphs" => self.read_value()?,
+                        "meta" => {
+                            if let Some(meta) = self.parse_value()? {
+                                return Ok(Some(Event::DocumentMeta(meta)));
+                            }
+                        }
+                        "prefixes" => {
+                            if let Some(prefixes) = self.parse_value()? {
+                                return Ok(Some(Event::DocumentPrefixes(prefixes)));
+                            }
+                        }
+                        _ => {
+                            let value = self.parse_value()?;
+                            return Ok(Some(Event::DocumentExtra(key, value)));
+                        }
+                    }
+                }
+                State::Graphs { first } => {
+                    if !self.next_item(first, b']')? {
+                        self.state = State::Document { first: false };
+                        continue;
+                    }
+                    self.expect(b'{')?;
+                    self.state = State::Graph { first: true };
+                    return Ok(Some(Event::StartGraph));
+                }
+                State::Graph { first } => {
+                    if !self.next_item(first, b'}')? {
+                        self.state = State::Graphs { first: false };
+                        return Ok(Some(Event::EndGraph));
+                    }
+                    self.state = State::Graph { first: false };
+                    let key = self.read_key()?;
+                    let items = match key.as_str() {
+                        "nodes" => Items::Nodes,
+                        "edges" => Items::Edges,
+                        "equivalentNodesSets" => Items::EquivalentNodesSets,
+                        "logicalDefinitionAxioms" => Items::LogicalDefinitionAxioms,
+                        "domainRangeAxioms" => Items::DomainRangeAxioms,
+                        "propertyChainAxioms" => Items::PropertyChainAxioms,
+                        "id" => return self.parse_value().map(Event::Id).map(Some),
+                        "lbl" => match self.parse_value()? {
+                            Some(label) => return Ok(Some(Event::Label(label))),
+                            None => continue,
+                        },
+                        "meta" => match self.parse_value()? {
+                            Some(meta) => return Ok(Some(Event::Meta(meta))),
+                            None => continue,
+                        },
+                        _ => {
+                            let value = self.parse_value()?;
+                            return Ok(Some(Event::Extra(key, value)));
+                        }
+                    };
+                    if self.peek_token()? == Some(b'[') {
+                        self.reader.consume(1);
+                        self.state = State::Items { first: true, items };
+                    } else {
+                        // a `null` array
+                        self.read_value()?;
+                    }
+                }
+                State::Items { first, items } => {
+                    if !self.next_item(first, b']')? {
+                        self.state = State::Graph { first: false };
+                        continue;
+                    }
+                    self.state = State::Items {
+                        first: false,
+                        items,
+                    };
+                    let event = match items {
+                        Items::Nodes => Event::Node(self.parse_value()?),
+                        Items::Edges => Event::Edge(self.parse_value()?),
+                        Items::EquivalentNodesSets => {
+                            Event::EquivalentNodesSet(self.parse_value()?)
+                        }
+                        Items::LogicalDefinitionAxioms => {
+                            Event::LogicalDefinitionAxiom(self.parse_value()?)
+                        }
+                        Items::DomainRangeAxioms => Event::DomainRangeAxiom(self.parse_value()?),
+                        Items::PropertyChainAxioms => {
+                            Event::PropertyChainAxiom(self.parse_value()?)
+                        }
+                    };
+                    return Ok(Some(event));
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for GraphReader<R> {
+    type Item = Result<Event>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.advance() {
+            Ok(event) => event.map(Ok),
+            Err(e) => {
+                // stop iterating after an error
+                self.state = State::Done;
+                Some(Err(e))
+            }
+        }
+    }
+}
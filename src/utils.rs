@@ -1,26 +1,495 @@
 pub mod serde {
+    use std::collections::BTreeMap;
+    use std::fmt::Formatter;
+    use std::fmt::Result as FmtResult;
+    use std::marker::PhantomData;
+
+    use serde::de::DeserializeSeed;
+    use serde::de::Error;
+    use serde::de::SeqAccess;
+    use serde::de::Visitor;
     use serde::Deserialize;
     use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+    use serde_json::Value;
+
+    use crate::model::DeserializeElement;
+    use crate::model::NodeType;
+    use crate::model::PropertyType;
+    use crate::model::SerializeElement;
+
+    /// A field of a data model element.
+    pub trait Field: Sized {
+        /// Check whether the field is omitted from compact serializations.
+        fn is_omitted(&self) -> bool;
+        /// Get the value of the field when missing from a serialization.
+        fn missing() -> Option<Self>;
+    }
+
+    impl Field for String {
+        fn is_omitted(&self) -> bool {
+            false
+        }
+        fn missing() -> Option<Self> {
+            None
+        }
+    }
+
+    impl Field for bool {
+        fn is_omitted(&self) -> bool {
+            !*self
+        }
+        fn missing() -> Option<Self> {
+            Some(false)
+        }
+    }
+
+    impl<T> Field for Option<T> {
+        fn is_omitted(&self) -> bool {
+            self.is_none()
+        }
+        fn missing() -> Option<Self> {
+            Some(None)
+        }
+    }
+
+    impl<T> Field for Vec<T> {
+        fn is_omitted(&self) -> bool {
+            self.is_empty()
+        }
+        fn missing() -> Option<Self> {
+            Some(Vec::new())
+        }
+    }
+
+    impl<K: Ord, V> Field for BTreeMap<K, V> {
+        fn is_omitted(&self) -> bool {
+            self.is_empty()
+        }
+        fn missing() -> Option<Self> {
+            Some(BTreeMap::new())
+        }
+    }
+
+    /// A reference to an element, serialized with the given verbosity.
+    pub struct Element<'a, T: ?Sized>(pub &'a T, pub bool);
+
+    impl<T: SerializeElement + ?Sized> Serialize for Element<'_, T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize_element(serializer, self.1)
+        }
+    }
+
+    /// A seed deserializing an element with the given strictness.
+    pub struct ElementSeed<T>(bool, PhantomData<T>);
+
+    impl<T> ElementSeed<T> {
+        pub fn new(strict: bool) -> Self {
+            Self(strict, PhantomData)
+        }
+    }
+
+    impl<'de, T: DeserializeElement<'de>> DeserializeSeed<'de> for ElementSeed<T> {
+        type Value = T;
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+            T::deserialize_element(deserializer, self.0)
+        }
+    }
+
+    /// A seed deserializing a vector possibly containing `null`.
+    pub struct NullableSeed<T>(bool, PhantomData<T>);
+
+    impl<T> NullableSeed<T> {
+        pub fn new(strict: bool) -> Self {
+            Self(strict, PhantomData)
+        }
+    }
+
+    impl<'de, T: DeserializeElement<'de>> DeserializeSeed<'de> for NullableSeed<Vec<T>> {
+        type Value = Vec<T>;
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<T>, D::Error> {
+            deserializer.deserialize_seq(SeqVisitor::<T>(self.0, true, PhantomData))
+        }
+    }
+
+    /// A seed deserializing the key of a field, known or unknown.
+    pub struct KeySeed(pub &'static [&'static str]);
+
+    impl<'de> DeserializeSeed<'de> for KeySeed {
+        type Value = Result<&'static str, String>;
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_str(self)
+        }
+    }
+
+    impl Visitor<'_> for KeySeed {
+        type Value = Result<&'static str, String>;
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("a field name")
+        }
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            match self.0.iter().find(|&&field| field == v) {
+                Some(field) => Ok(Ok(field)),
+                None => Ok(Err(v.to_string())),
+            }
+        }
+    }
+
+    /// Implement element serialization for types with a plain serde implementation.
+    macro_rules! impl_plain {
+        ($($ty:ty),*) => {
+            $(
+                impl SerializeElement for $ty {
+                    fn serialize_element<S: Serializer>(&self, serializer: S, _verbose: bool) -> Result<S::Ok, S::Error> {
+                        self.serialize(serializer)
+                    }
+                }
+
+                impl<'de> DeserializeElement<'de> for $ty {
+                    fn deserialize_element<D: Deserializer<'de>>(deserializer: D, _strict: bool) -> Result<Self, D::Error> {
+                        Self::deserialize(deserializer)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_plain!(
+        String,
+        bool,
+        Value,
+        NodeType,
+        PropertyType,
+        BTreeMap<String, String>
+    );
+
+    impl<T: SerializeElement + ?Sized> SerializeElement for &T {
+        fn serialize_element<S: Serializer>(
+            &self,
+            serializer: S,
+            verbose: bool,
+        ) -> Result<S::Ok, S::Error> {
+            (**self).serialize_element(serializer, verbose)
+        }
+    }
+
+    impl<T: SerializeElement> SerializeElement for Box<T> {
+        fn serialize_element<S: Serializer>(
+            &self,
+            serializer: S,
+            verbose: bool,
+        ) -> Result<S::Ok, S::Error> {
+            (**self).serialize_element(serializer, verbose)
+        }
+    }
+
+    impl<'de, T: DeserializeElement<'de>> DeserializeElement<'de> for Box<T> {
+        fn deserialize_element<D: Deserializer<'de>>(
+            deserializer: D,
+            strict: bool,
+        ) -> Result<Self, D::Error> {
+            T::deserialize_element(deserializer, strict).map(Box::new)
+        }
+    }
+
+    impl<T: SerializeElement> SerializeElement for Option<T> {
+        fn serialize_element<S: Serializer>(
+            &self,
+            serializer: S,
+            verbose: bool,
+        ) -> Result<S::Ok, S::Error> {
+            match self {
+                Some(value) => serializer.serialize_some(&Element(value, verbose)),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+
+    impl<'de, T: DeserializeElement<'de>> DeserializeElement<'de> for Option<T> {
+        fn deserialize_element<D: Deserializer<'de>>(
+            deserializer: D,
+            strict: bool,
+        ) -> Result<Self, D::Error> {
+            deserializer.deserialize_option(OptionVisitor::<T>(strict, PhantomData))
+        }
+    }
+
+    impl<T: SerializeElement> SerializeElement for Vec<T> {
+        fn serialize_element<S: Serializer>(
+            &self,
+            serializer: S,
+            verbose: bool,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(|item| Element(item, verbose)))
+        }
+    }
 
     /// Deserialize a possibly missing vector into an empty one.
-    pub fn optional_vector<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
-    where
-        D: Deserializer<'de>,
-        T: Deserialize<'de>,
-    {
-        match Option::deserialize(deserializer) {
-            Ok(Some(v)) => Ok(v),
-            Ok(None) => Ok(Vec::new()),
-            Err(e) => Err(e),
-        }
-    }
-
-    /// Deserialize a vector possibly containing `null`.
-    pub fn nullable_vector<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
-    where
-        D: Deserializer<'de>,
-        T: Deserialize<'de>,
-    {
-        Vec::<Option<T>>::deserialize(deserializer).map(|v| v.into_iter().flatten().collect())
+    impl<'de, T: DeserializeElement<'de>> DeserializeElement<'de> for Vec<T> {
+        fn deserialize_element<D: Deserializer<'de>>(
+            deserializer: D,
+            strict: bool,
+        ) -> Result<Self, D::Error> {
+            let seq = SeqVisitor::<T>(strict, false, PhantomData);
+            deserializer
+                .deserialize_option(OptionSeqVisitor(seq))
+                .map(Option::unwrap_or_default)
+        }
+    }
+
+    /// A visitor for optional elements.
+    struct OptionVisitor<T>(bool, PhantomData<T>);
+
+    impl<'de, T: DeserializeElement<'de>> Visitor<'de> for OptionVisitor<T> {
+        type Value = Option<T>;
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("an optional value")
+        }
+        fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+        fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            T::deserialize_element(deserializer, self.0).map(Some)
+        }
+    }
+
+    /// A visitor for optional sequences of elements.
+    struct OptionSeqVisitor<T>(SeqVisitor<T>);
+
+    impl<'de, T: DeserializeElement<'de>> Visitor<'de> for OptionSeqVisitor<T> {
+        type Value = Option<Vec<T>>;
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("an optional sequence")
+        }
+        fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+        fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self.0).map(Some)
+        }
+    }
+
+    /// A visitor for sequences of elements, possibly skipping `null` items.
+    struct SeqVisitor<T>(bool, bool, PhantomData<T>);
+
+    impl<'de, T: DeserializeElement<'de>> Visitor<'de> for SeqVisitor<T> {
+        type Value = Vec<T>;
+        fn expecting(&self, f: &mut Formatter) -> FmtResult {
+            f.write_str("a sequence")
+        }
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            if self.1 {
+                let seed = || ElementSeed::<Option<T>>::new(self.0);
+                while let Some(item) = seq.next_element_seed(seed())? {
+                    items.extend(item);
+                }
+            } else {
+                while let Some(item) = seq.next_element_seed(ElementSeed::new(self.0))? {
+                    items.push(item);
+                }
+            }
+            Ok(items)
+        }
+    }
+
+    /// Implement serde for a data model element with an `extra` field.
+    ///
+    /// Fields are listed in serialization order with their serialized name.
+    /// Unknown fields are collected into `extra` while the element is being
+    /// deserialized, without buffering the element first.
+    macro_rules! impl_element {
+        ($ty:ident { $($field:ident: $name:literal $(with $seed:ident)?),* $(,)? }) => {
+            impl $crate::model::SerializeElement for $ty {
+                fn serialize_element<S>(&self, serializer: S, verbose: bool) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    use ::serde::ser::SerializeMap;
+                    use $crate::utils::serde::Element;
+                    use $crate::utils::serde::Field;
+
+                    let mut map = serializer.serialize_map(None)?;
+                    $(
+                        if verbose || !Field::is_omitted(&self.$field) {
+                            map.serialize_entry($name, &Element(&self.$field, verbose))?;
+                        }
+                    )*
+                    for (key, value) in self.extra.iter() {
+                        map.serialize_entry(key, value)?;
+                    }
+                    map.end()
+                }
+            }
+
+            impl<'de> $crate::model::DeserializeElement<'de> for $ty {
+                fn deserialize_element<D>(deserializer: D, strict: bool) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    const FIELDS: &[&str] = &[$($name),*];
+
+                    struct ElementVisitor(bool);
+
+                    impl<'de> ::serde::de::Visitor<'de> for ElementVisitor {
+                        type Value = $ty;
+
+                        fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                            f.write_str(concat!("struct ", stringify!($ty)))
+                        }
+
+                        fn visit_map<A>(self, mut map: A) -> Result<$ty, A::Error>
+                        where
+                            A: ::serde::de::MapAccess<'de>,
+                        {
+                            use ::serde::de::Error;
+                            use $crate::utils::serde::Field;
+                            use $crate::utils::serde::KeySeed;
+
+                            $( let mut $field = None; )*
+                            let mut extra = ::std::collections::BTreeMap::new();
+                            while let Some(key) = map.next_key_seed(KeySeed(FIELDS))? {
+                                match key {
+                                    $(
+                                        Ok($name) if $field.is_some() => {
+                                            return Err(A::Error::duplicate_field($name));
+                                        }
+                                        Ok($name) => {
+                                            let seed = impl_element!(@seed self.0 $(, $seed)?);
+                                            $field = Some(map.next_value_seed(seed)?);
+                                        }
+                                    )*
+                                    Ok(_) => unreachable!(),
+                                    Err(key) if self.0 => {
+                                        return Err(A::Error::unknown_field(&key, FIELDS));
+                                    }
+                                    Err(key) => {
+                                        extra.insert(key, map.next_value()?);
+                                    }
+                                }
+                            }
+
+                            Ok($ty {
+                                $(
+                                    $field: match $field.or_else(Field::missing) {
+                                        Some(value) => value,
+                                        None => return Err(A::Error::missing_field($name)),
+                                    },
+                                )*
+                                extra,
+                            })
+                        }
+                    }
+
+                    deserializer.deserialize_struct(stringify!($ty), FIELDS, ElementVisitor(strict))
+                }
+            }
+
+            impl ::serde::Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: ::serde::Serializer,
+                {
+                    $crate::model::SerializeElement::serialize_element(self, serializer, false)
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    $crate::model::DeserializeElement::deserialize_element(deserializer, false)
+                }
+            }
+        };
+        (@seed $strict:expr) => {
+            $crate::utils::serde::ElementSeed::new($strict)
+        };
+        (@seed $strict:expr, $seed:ident) => {
+            $crate::utils::serde::$seed::new($strict)
+        };
+    }
+
+    pub(crate) use impl_element;
+}
+
+pub mod io {
+    use std::io::BufRead;
+    use std::io::Result;
+    use std::path::Path;
+
+    /// A compression format detected for graph files.
+    ///
+    /// Formats are always detected, even when the feature required to
+    /// support them is disabled, so that an explicit error can be returned.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Compression {
+        Gzip,
+        Zstd,
+    }
+
+    impl Compression {
+        /// Get the name of the cargo feature supporting this format.
+        pub fn feature(self) -> &'static str {
+            match self {
+                Compression::Gzip => "gzip",
+                Compression::Zstd => "zstd",
+            }
+        }
+
+        /// Check whether the feature supporting this format is enabled.
+        pub fn is_supported(self) -> bool {
+            match self {
+                Compression::Gzip => cfg!(feature = "gzip"),
+                Compression::Zstd => cfg!(feature = "zstd"),
+            }
+        }
+
+        /// Detect the compression of a file from its extension.
+        pub fn from_path(path: &Path) -> Option<Self> {
+            match path.extension().and_then(|ext| ext.to_str()) {
+                Some("gz") => Some(Compression::Gzip),
+                Some("zst") => Some(Compression::Zstd),
+                _ => None,
+            }
+        }
+
+        /// Detect the compression of a stream from its magic bytes.
+        pub fn from_reader<R: BufRead>(reader: &mut R) -> Result<Option<Self>> {
+            let magic = reader.fill_buf()?;
+            if magic.starts_with(&[0x1f, 0x8b]) {
+                Ok(Some(Compression::Gzip))
+            } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+                Ok(Some(Compression::Zstd))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    /// Get the serialization format extension of a possibly compressed file.
+    pub fn format_extension(path: &Path) -> Option<&str> {
+        let path = match Compression::from_path(path) {
+            Some(_) => Path::new(path.file_stem()?),
+            None => path,
+        };
+        path.extension().and_then(|ext| ext.to_str())
     }
 }
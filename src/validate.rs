@@ -0,0 +1,378 @@
+//! Validation of OBO graphs against common ontology quality checks.
+//!
+//! The checks are modeled after the default profile of `robot report`, so
+//! that a graph can be linted without converting it to OWL first. Each
+//! [`Finding`] names the offending entity and has a [`Severity`], which
+//! makes it easy to fail a CI job only on errors.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use crate::constants::property::iao;
+use crate::constants::property::obo_in_owl;
+use crate::model::BasicPropertyValue;
+use crate::model::GraphDocument;
+use crate::model::Meta;
+use crate::model::Node;
+use crate::model::NodeType;
+
+/// The severity of a validation finding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// A finding which should be fixed, but does not break the graph.
+    Warning,
+    /// A finding which must be fixed.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Severity::Warning => f.write_str("WARN"),
+            Severity::Error => f.write_str("ERROR"),
+        }
+    }
+}
+
+/// A validation check performed by [`validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Check {
+    /// An edge refers to a subject or object which is not a node of the document.
+    DanglingEdge,
+    /// A node is declared more than once in the same graph.
+    DuplicateId,
+    /// A node has no label.
+    MissingLabel,
+    /// A label is shared by several nodes of the same namespace.
+    DuplicateLabel,
+    /// A node has more than one definition.
+    MultipleDefinitions,
+    /// A node has a definition without any text.
+    EmptyDefinition,
+    /// A deprecated node is used as the subject or object of an edge.
+    DeprecatedReference,
+    /// A deprecated node has no replacement.
+    MissingReplacement,
+    /// A synonym has a predicate which is not a synonym scope.
+    InvalidSynonymType,
+    /// A domain and range axiom is declared for a node which is not a property.
+    InvalidDomainRange,
+}
+
+impl Check {
+    /// Get the severity of the findings of this check.
+    pub fn severity(self) -> Severity {
+        match self {
+            Check::EmptyDefinition | Check::MissingReplacement => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(match self {
+            Check::DanglingEdge => "dangling_edge",
+            Check::DuplicateId => "duplicate_id",
+            Check::MissingLabel => "missing_label",
+            Check::DuplicateLabel => "duplicate_label",
+            Check::MultipleDefinitions => "multiple_definitions",
+            Check::EmptyDefinition => "empty_definition",
+            Check::DeprecatedReference => "deprecated_reference",
+            Check::MissingReplacement => "missing_replacement",
+            Check::InvalidSynonymType => "invalid_synonym_type",
+            Check::InvalidDomainRange => "invalid_domain_range",
+        })
+    }
+}
+
+/// A problem found in a graph document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// The check which produced the finding.
+    pub check: Check,
+    /// The severity of the finding.
+    pub severity: Severity,
+    /// The identifier of the offending entity.
+    pub id: String,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl Finding {
+    fn new<I, M>(check: Check, id: I, message: M) -> Self
+    where
+        I: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            check,
+            severity: check.severity(),
+            id: id.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.severity, self.check, self.id, self.message
+        )
+    }
+}
+
+/// The findings of the validation of a graph document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    findings: Vec<Finding>,
+}
+
+impl Report {
+    /// Get all the findings.
+    ///
+    /// Findings are grouped by check, in the order of the [`Check`]
+    /// variants, and the findings of a check follow the order of the
+    /// document.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// Iterate over the findings with the given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |f| f.severity == severity)
+    }
+
+    /// Iterate over the findings of the given check.
+    pub fn with_check(&self, check: Check) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |f| f.check == check)
+    }
+
+    /// Get the highest severity of the findings, if any.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+
+    /// Check whether any finding is an error.
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Get the number of findings.
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// Check whether the document passed all checks.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    fn push<I, M>(&mut self, check: Check, id: I, message: M)
+    where
+        I: Into<String>,
+        M: Into<String>,
+    {
+        self.findings.push(Finding::new(check, id, message));
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        for finding in self.findings.iter() {
+            writeln!(f, "{}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for Report {
+    type Item = Finding;
+    type IntoIter = std::vec::IntoIter<Finding>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.findings.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Report {
+    type Item = &'a Finding;
+    type IntoIter = std::slice::Iter<'a, Finding>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.findings.iter()
+    }
+}
+
+/// The synonym predicates, as compact or full identifiers.
+const SYNONYM_PREDICATES: &[&str] = &[
+    "hasBroadSynonym",
+    "hasExactSynonym",
+    "hasNarrowSynonym",
+    "hasRelatedSynonym",
+    obo_in_owl::HAS_BROAD_SYNONYM,
+    obo_in_owl::HAS_EXACT_SYNONYM,
+    obo_in_owl::HAS_NARROW_SYNONYM,
+    obo_in_owl::HAS_RELATED_SYNONYM,
+];
+
+/// Validate a graph document.
+///
+/// Edges, deprecated nodes and domain and range axioms are resolved against
+/// the nodes of all the graphs of the document, while duplicate identifiers
+/// and labels are only reported within a single graph. Deprecated nodes are
+/// not checked for duplicate labels.
+pub fn validate(doc: &GraphDocument) -> Report {
+    let mut report = Report::default();
+
+    let mut nodes: HashMap<&str, &Node> = HashMap::new();
+    for node in doc.graphs.iter().flat_map(|g| g.nodes.iter()) {
+        nodes.entry(node.id.as_str()).or_insert(node);
+    }
+
+    // edges
+    for edge in doc.graphs.iter().flat_map(|g| g.edges.iter()) {
+        let triple = format!("{} {} {}", edge.sub, edge.pred, edge.obj);
+        for (role, id) in [("subject", &edge.sub), ("object", &edge.obj)] {
+            match nodes.get(id.as_str()) {
+                None => report.push(
+                    Check::DanglingEdge,
+                    id.as_str(),
+                    format!("undeclared {} of edge `{}`", role, triple),
+                ),
+                Some(node) if is_deprecated(node) => report.push(
+                    Check::DeprecatedReference,
+                    id.as_str(),
+                    format!("deprecated node used as {} of edge `{}`", role, triple),
+                ),
+                Some(_) => (),
+            }
+        }
+    }
+
+    // nodes
+    for graph in doc.graphs.iter() {
+        let mut seen = HashSet::new();
+        let mut labels: HashMap<(Option<&str>, &str), Vec<&str>> = HashMap::new();
+        for node in graph.nodes.iter() {
+            let id = node.id.as_str();
+            if !seen.insert(id) {
+                report.push(Check::DuplicateId, id, "node declared more than once");
+            }
+
+            let deprecated = is_deprecated(node);
+            match node.label.as_deref() {
+                None => report.push(Check::MissingLabel, id, "node has no label"),
+                Some(label) if !deprecated => {
+                    let key = (namespace(node), label);
+                    labels.entry(key).or_default().push(id);
+                }
+                Some(_) => (),
+            }
+
+            let meta = match node.meta.as_deref() {
+                Some(meta) => meta,
+                None => continue,
+            };
+
+            let definitions = meta.definition.iter().count()
+                + properties(meta, &[iao::DEFINITION, obo_in_owl::HAS_DEFINITION]).count();
+            if definitions > 1 {
+                let message = format!("node has {} definitions", definitions);
+                report.push(Check::MultipleDefinitions, id, message);
+            }
+            let texts = meta.definition.iter().map(|def| def.val.as_str()).chain(
+                properties(meta, &[iao::DEFINITION, obo_in_owl::HAS_DEFINITION])
+                    .map(|pv| pv.val.as_str()),
+            );
+            for text in texts {
+                if text.trim().is_empty() {
+                    report.push(Check::EmptyDefinition, id, "definition is empty");
+                }
+            }
+
+            if deprecated
+                && properties(meta, &[iao::REPLACED_BY, obo_in_owl::REPLACED_BY])
+                    .next()
+                    .is_none()
+            {
+                report.push(Check::MissingReplacement, id, "no `replacedBy` given");
+            }
+
+            for synonym in meta.synonyms.iter() {
+                if !SYNONYM_PREDICATES.contains(&synonym.pred.as_str()) {
+                    let message = format!(
+                        "synonym {:?} has unknown predicate `{}`",
+                        synonym.val, synonym.pred
+                    );
+                    report.push(Check::InvalidSynonymType, id, message);
+                }
+            }
+        }
+
+        // report shared labels in the order of the nodes
+        for node in graph.nodes.iter().filter(|n| !is_deprecated(n)) {
+            let id = node.id.as_str();
+            let label = match node.label.as_deref() {
+                Some(label) => label,
+                None => continue,
+            };
+            let ids = &labels[&(namespace(node), label)];
+            if ids.len() > 1 {
+                let others = ids.iter().filter(|&&other| other != id);
+                let message = format!(
+                    "label {:?} is also used by {}",
+                    label,
+                    others.copied().collect::<Vec<_>>().join(", ")
+                );
+                report.push(Check::DuplicateLabel, id, message);
+            }
+        }
+    }
+
+    // axioms
+    for axiom in doc.graphs.iter().flat_map(|g| g.domain_range_axioms.iter()) {
+        let id = axiom.predicate_id.as_str();
+        let message = match nodes.get(id) {
+            None => "domain and range declared for an undeclared node",
+            Some(node) if !is_property(node) => "domain and range declared for a non-property node",
+            Some(_) => continue,
+        };
+        report.push(Check::InvalidDomainRange, id, message);
+    }
+
+    // group the findings by check, keeping the order of the document
+    report.findings.sort_by_key(|f| f.check);
+    report
+}
+
+/// Check whether a node is deprecated.
+fn is_deprecated(node: &Node) -> bool {
+    node.meta.as_ref().map(|m| m.deprecated).unwrap_or(false)
+}
+
+/// Check whether a node is declared as a property.
+fn is_property(node: &Node) -> bool {
+    node.ty == Some(NodeType::Property) || node.property_type.is_some()
+}
+
+/// Get the OBO namespace of a node, if any.
+fn namespace(node: &Node) -> Option<&str> {
+    let meta = node.meta.as_deref()?;
+    properties(meta, &[obo_in_owl::HAS_OBO_NAMESPACE])
+        .next()
+        .map(|pv| pv.val.as_str())
+}
+
+/// Iterate over the basic property values with any of the given predicates.
+fn properties<'a>(
+    meta: &'a Meta,
+    preds: &'a [&'a str],
+) -> impl Iterator<Item = &'a BasicPropertyValue> {
+    meta.basic_property_values
+        .iter()
+        .filter(move |pv| preds.contains(&pv.pred.as_str()))
+}
@@ -0,0 +1,708 @@
+extern crate fastobo_graphs;
+
+use fastobo_graphs::algo::Hierarchy;
+use fastobo_graphs::algo::PredicateSet;
+use fastobo_graphs::model::Graph;
+
+use std::iter::FromIterator;
+
+//        E
+//       / \
+//      D   F
+//     / \  :
+//    B   C      (`:` is `part_of`)
+//     \ /
+//      A
+const JSON: &str = r#"{"graphs": [{
+    "id": "http://purl.obolibrary.org/obo/test.owl",
+    "nodes": [
+        {"id": "A", "lbl": "a"},
+        {"id": "B", "lbl": "b"},
+        {"id": "C", "lbl": "c"},
+        {"id": "D", "lbl": "d"},
+        {"id": "E", "lbl": "e"},
+        {"id": "F", "lbl": "f"},
+        {"id": "X", "lbl": "x"},
+        {"id": "Y", "lbl": "y"}
+    ],
+    "edges": [
+        {"sub": "A", "pred": "is_a", "obj": "C"},
+        {"sub": "A", "pred": "is_a", "obj": "B"},
+        {"sub": "B", "pred": "is_a", "obj": "D"},
+        {"sub": "C", "pred": "http://www.w3.org/2000/01/rdf-schema#subClassOf", "obj": "D"},
+        {"sub": "C", "pred": "http://purl.obolibrary.org/obo/BFO_0000050", "obj": "F"},
+        {"sub": "D", "pred": "is_a", "obj": "E"},
+        {"sub": "F", "pred": "is_a", "obj": "E"},
+        {"sub": "X", "pred": "is_a", "obj": "Y"},
+        {"sub": "Y", "pred": "is_a", "obj": "X"}
+    ]
+}]}"#;
+
+fn graph() -> Graph {
+    let doc = fastobo_graphs::from_str(JSON).unwrap();
+    doc.graphs.into_iter().next().unwrap()
+}
+
+fn is_a_part_of() -> PredicateSet {
+    vec!["is_a", "BFO:0000050"].into_iter().collect()
+}
+
+mod predicates {
+    use super::*;
+
+    #[test]
+    fn normalization() {
+        let preds = is_a_part_of();
+        assert!(preds.contains("rdfs:subClassOf"));
+        assert!(preds.contains("http://www.w3.org/2000/01/rdf-schema#subClassOf"));
+        assert!(preds.contains("http://purl.obolibrary.org/obo/BFO_0000050"));
+        assert!(!preds.contains("part_of"));
+        assert_eq!(
+            preds.iter().collect::<Vec<_>>(),
+            vec!["BFO:0000050", "is_a"]
+        );
+    }
+}
+
+mod traverse {
+    use super::*;
+
+    #[test]
+    fn parents_children() {
+        let g = graph();
+        let h = Hierarchy::new(&g, &PredicateSet::is_a());
+        assert_eq!(h.parents("A"), &["B", "C"]);
+        assert_eq!(h.parents("C"), &["D"]);
+        assert_eq!(h.children("D"), &["B", "C"]);
+        assert!(h.parents("E").is_empty());
+        assert!(h.children("Z").is_empty());
+        assert_eq!(
+            fastobo_graphs::algo::parents(&g, "C", &is_a_part_of()),
+            vec!["D", "F"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::children(&g, "F", &is_a_part_of()),
+            vec!["C"]
+        );
+    }
+
+    #[test]
+    fn ancestors() {
+        let g = graph();
+        let preds = PredicateSet::is_a();
+        assert_eq!(
+            fastobo_graphs::algo::ancestors(&g, "A", &preds, None),
+            vec!["B", "C", "D", "E"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::ancestors(&g, "A", &is_a_part_of(), None),
+            vec!["B", "C", "D", "F", "E"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::ancestors(&g, "A", &preds, Some(2)),
+            vec!["B", "C", "D"]
+        );
+        assert!(fastobo_graphs::algo::ancestors(&g, "A", &preds, Some(0)).is_empty());
+        assert!(fastobo_graphs::algo::ancestors(&g, "E", &preds, None).is_empty());
+    }
+
+    #[test]
+    fn descendants() {
+        let g = graph();
+        let preds = is_a_part_of();
+        assert_eq!(
+            fastobo_graphs::algo::descendants(&g, "E", &preds, None),
+            vec!["D", "F", "B", "C", "A"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::descendants(&g, "E", &preds, Some(1)),
+            vec!["D", "F"]
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let g = graph();
+        let preds = PredicateSet::is_a();
+        assert_eq!(
+            fastobo_graphs::algo::ancestors(&g, "X", &preds, None),
+            vec!["Y"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::descendants(&g, "Y", &preds, None),
+            vec!["X"]
+        );
+    }
+}
+
+mod closure {
+    use super::*;
+
+    use fastobo_graphs::algo::Closure;
+
+    const TRANSITIVE: &str = r#"{"graphs": [{
+        "id": "http://purl.obolibrary.org/obo/test.owl",
+        "nodes": [
+            {"id": "http://purl.obolibrary.org/obo/BFO_0000050", "type": "PROPERTY", "meta": {
+                "basicPropertyValues": [{
+                    "pred": "http://www.geneontology.org/formats/oboInOwl#is_transitive",
+                    "val": "true"
+                }]
+            }},
+            {"id": "http://purl.obolibrary.org/obo/RO_0002202", "type": "PROPERTY"},
+            {"id": "http://purl.obolibrary.org/obo/RO_0002207", "type": "PROPERTY"}
+        ],
+        "edges": [
+            {"sub": "A", "pred": "BFO:0000050", "obj": "B"},
+            {"sub": "B", "pred": "BFO:0000050", "obj": "C"},
+            {"sub": "C", "pred": "is_a", "obj": "D"},
+            {"sub": "E", "pred": "RO:0002202", "obj": "F"},
+            {"sub": "F", "pred": "RO:0002202", "obj": "G"},
+            {"sub": "G", "pred": "BFO:0000050", "obj": "H"}
+        ],
+        "propertyChainAxioms": [{
+            "predicateId": "http://purl.obolibrary.org/obo/RO_0002207",
+            "chainPredicateIds": [
+                "http://purl.obolibrary.org/obo/RO_0002202",
+                "http://purl.obolibrary.org/obo/BFO_0000050"
+            ]
+        }]
+    }]}"#;
+
+    #[test]
+    fn is_a() {
+        let g = graph();
+        let closure = Closure::new(&g, &PredicateSet::is_a());
+        let inferred = closure.inferred().collect::<Vec<_>>();
+        assert_eq!(
+            inferred,
+            vec![
+                ("A", "is_a", "D"),
+                ("A", "is_a", "E"),
+                ("B", "is_a", "E"),
+                ("C", "is_a", "E"),
+            ]
+        );
+        assert_eq!(closure.triples().count(), 12);
+        assert!(closure.triples().all(|(s, _, o)| s != o));
+    }
+
+    #[test]
+    fn propagation() {
+        let g = graph();
+        let preds = PredicateSet::from_iter(&["BFO:0000050"]);
+        let closure = fastobo_graphs::algo::closure(&g, &preds);
+        let part_of = "http://purl.obolibrary.org/obo/BFO_0000050";
+        assert_eq!(
+            closure.triples().collect::<Vec<_>>(),
+            vec![
+                ("A", part_of, "E"),
+                ("A", part_of, "F"),
+                ("C", part_of, "E"),
+                ("C", part_of, "F"),
+            ]
+        );
+    }
+
+    #[test]
+    fn transitivity() {
+        let g = fastobo_graphs::from_str(TRANSITIVE)
+            .unwrap()
+            .graphs
+            .remove(0);
+        let closure = Closure::new(&g, &is_a_part_of());
+        assert_eq!(
+            closure.inferred().collect::<Vec<_>>(),
+            vec![
+                ("A", "BFO:0000050", "C"),
+                ("A", "BFO:0000050", "D"),
+                ("B", "BFO:0000050", "D"),
+            ]
+        );
+
+        // `RO:0002202` is not declared transitive
+        let preds = PredicateSet::from_iter(&["RO:0002202"]);
+        let closure = Closure::new(&g, &preds);
+        assert_eq!(closure.inferred().count(), 0);
+    }
+
+    #[test]
+    fn explicit_transitivity() {
+        let mut g = fastobo_graphs::from_str(TRANSITIVE)
+            .unwrap()
+            .graphs
+            .remove(0);
+        // graphs exported by other tools do not declare transitivity
+        g.nodes.clear();
+        let preds = is_a_part_of();
+        assert_eq!(
+            Closure::new(&g, &preds).inferred().collect::<Vec<_>>(),
+            vec![("B", "BFO:0000050", "D")]
+        );
+
+        let transitive = PredicateSet::from_iter(&["BFO:0000050"]);
+        let closure = Closure::with_transitive(&g, &preds, &transitive);
+        assert_eq!(
+            closure.inferred().collect::<Vec<_>>(),
+            vec![
+                ("A", "BFO:0000050", "C"),
+                ("A", "BFO:0000050", "D"),
+                ("B", "BFO:0000050", "D"),
+            ]
+        );
+    }
+
+    #[test]
+    fn property_chain() {
+        let g = fastobo_graphs::from_str(TRANSITIVE)
+            .unwrap()
+            .graphs
+            .remove(0);
+        let preds = PredicateSet::from_iter(&["RO:0002207"]);
+        let closure = Closure::new(&g, &preds);
+        assert_eq!(
+            closure.inferred().collect::<Vec<_>>(),
+            vec![("F", "http://purl.obolibrary.org/obo/RO_0002207", "H")]
+        );
+    }
+
+    #[test]
+    fn to_graph() {
+        let g = graph();
+        let inferred = Closure::new(&g, &PredicateSet::is_a()).to_graph();
+        assert_eq!(inferred.id, g.id);
+        assert!(inferred.nodes.is_empty());
+        assert_eq!(inferred.edges.len(), 4);
+        for edge in inferred.edges.iter() {
+            let pv = &edge.meta.as_ref().unwrap().basic_property_values[0];
+            assert_eq!(
+                pv.pred,
+                "http://www.geneontology.org/formats/oboInOwl#is_inferred"
+            );
+            assert_eq!(pv.val, "true");
+        }
+    }
+}
+
+mod reduction {
+    use super::*;
+
+    use fastobo_graphs::algo::Reduction;
+
+    const REDUNDANT: &str = r#"{"graphs": [{
+        "id": "http://purl.obolibrary.org/obo/test.owl",
+        "edges": [
+            {"sub": "A", "pred": "is_a", "obj": "B"},
+            {"sub": "A", "pred": "is_a", "obj": "D"},
+            {"sub": "A", "pred": "is_a", "obj": "F"},
+            {"sub": "A", "pred": "BFO:0000050", "obj": "C"},
+            {"sub": "B", "pred": "is_a", "obj": "D"},
+            {"sub": "B", "pred": "is_a", "obj": "D"},
+            {"sub": "C", "pred": "BFO:0000050", "obj": "F"},
+            {"sub": "P", "pred": "is_a", "obj": "Q"},
+            {"sub": "P", "pred": "is_a", "obj": "R"},
+            {"sub": "Q", "pred": "is_a", "obj": "R"},
+            {"sub": "R", "pred": "is_a", "obj": "Q"}
+        ]
+    }]}"#;
+
+    fn redundant(reduction: &Reduction) -> Vec<(String, Vec<String>)> {
+        reduction
+            .redundant()
+            .iter()
+            .map(|r| {
+                let e = r.edge;
+                let path = r.path.iter().map(|e| format!("{}>{}", e.sub, e.obj));
+                (format!("{}>{}", e.sub, e.obj), path.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn by_predicate() {
+        let g = fastobo_graphs::from_str(REDUNDANT)
+            .unwrap()
+            .graphs
+            .remove(0);
+        let reduction = Reduction::by_predicate(&g, &is_a_part_of());
+        assert_eq!(
+            redundant(&reduction),
+            vec![
+                (
+                    "A>D".to_string(),
+                    vec!["A>B".to_string(), "B>D".to_string()]
+                ),
+                ("B>D".to_string(), vec!["B>D".to_string()]),
+            ]
+        );
+        let positions = reduction.redundant().iter().map(|r| r.index);
+        assert_eq!(positions.collect::<Vec<_>>(), vec![1, 5]);
+        assert!(reduction.is_redundant(1));
+        assert!(!reduction.is_redundant(2));
+        assert!(reduction.is_redundant(5));
+        assert!(!reduction.is_redundant(4));
+    }
+
+    #[test]
+    fn combined() {
+        let g = fastobo_graphs::from_str(REDUNDANT)
+            .unwrap()
+            .graphs
+            .remove(0);
+        let reduction = fastobo_graphs::algo::transitive_reduction(&g, &is_a_part_of());
+        let edges = redundant(&reduction)
+            .into_iter()
+            .map(|(e, _)| e)
+            .collect::<Vec<_>>();
+        assert_eq!(edges, vec!["A>D", "A>F", "B>D"]);
+        assert_eq!(reduction.edges().count(), 8);
+    }
+
+    #[test]
+    fn predicates() {
+        let g = fastobo_graphs::from_str(REDUNDANT)
+            .unwrap()
+            .graphs
+            .remove(0);
+        let reduction = Reduction::new(&g, &PredicateSet::is_a());
+        assert_eq!(reduction.redundant().len(), 2);
+        // `part_of` edges are kept in the graph but not in the hierarchy
+        assert_eq!(reduction.to_graph().edges.len(), 9);
+        assert_eq!(reduction.edges().count(), 7);
+    }
+
+    #[test]
+    fn cycle_with_shared_parent() {
+        let g = fastobo_graphs::from_str(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "edges": [
+                    {"sub": "X", "pred": "is_a", "obj": "Y"},
+                    {"sub": "Y", "pred": "is_a", "obj": "X"},
+                    {"sub": "X", "pred": "is_a", "obj": "Z"},
+                    {"sub": "Y", "pred": "is_a", "obj": "Z"}
+                ]
+            }]}"#,
+        )
+        .unwrap()
+        .graphs
+        .remove(0);
+        let preds = PredicateSet::is_a();
+        let reduced = Reduction::new(&g, &preds).to_graph();
+        for id in ["X", "Y"] {
+            let ancestors = fastobo_graphs::algo::ancestors(&reduced, id, &preds, None);
+            assert!(ancestors.contains(&"Z"), "{} lost Z: {:?}", id, ancestors);
+        }
+    }
+}
+
+mod lca {
+    use super::*;
+
+    #[test]
+    fn lowest_common_ancestors() {
+        let g = graph();
+        let h = Hierarchy::new(&g, &PredicateSet::is_a());
+        assert_eq!(h.common_ancestors("B", "C"), vec!["D", "E"]);
+        assert_eq!(h.lowest_common_ancestors("B", "C"), vec!["D"]);
+        assert_eq!(h.lowest_common_ancestors("B", "D"), vec!["D"]);
+        assert_eq!(h.lowest_common_ancestors("A", "F"), vec!["E"]);
+        assert!(h.lowest_common_ancestors("A", "X").is_empty());
+        assert!(h.lowest_common_ancestors("A", "Z").is_empty());
+        assert_eq!(
+            fastobo_graphs::algo::lowest_common_ancestors(&g, "A", "F", &is_a_part_of()),
+            vec!["F"]
+        );
+    }
+
+    #[test]
+    fn several_ancestors() {
+        let g = fastobo_graphs::from_str(
+            r#"{"graphs": [{"id": "test", "edges": [
+                {"sub": "A", "pred": "is_a", "obj": "C"},
+                {"sub": "A", "pred": "is_a", "obj": "D"},
+                {"sub": "B", "pred": "is_a", "obj": "C"},
+                {"sub": "B", "pred": "is_a", "obj": "D"},
+                {"sub": "C", "pred": "is_a", "obj": "E"},
+                {"sub": "D", "pred": "is_a", "obj": "E"}
+            ]}]}"#,
+        )
+        .unwrap()
+        .graphs
+        .remove(0);
+        let h = Hierarchy::new(&g, &PredicateSet::is_a());
+        assert_eq!(h.lowest_common_ancestors("A", "B"), vec!["C", "D"]);
+    }
+
+    #[test]
+    fn shortest_path() {
+        let g = graph();
+        let h = Hierarchy::new(&g, &PredicateSet::is_a());
+
+        let path = h.shortest_path("B", "C").unwrap();
+        assert_eq!(path.ancestor, "D");
+        assert_eq!(path.nodes, vec!["B", "D", "C"]);
+        assert_eq!(path.len(), 2);
+        assert_eq!(
+            (path.edges[1].sub.as_str(), path.edges[1].obj.as_str()),
+            ("C", "D")
+        );
+
+        let path = h.shortest_path("A", "E").unwrap();
+        assert_eq!(path.ancestor, "E");
+        assert_eq!(path.nodes, vec!["A", "B", "D", "E"]);
+
+        let path = h.shortest_path("A", "A").unwrap();
+        assert!(path.is_empty());
+        assert_eq!(path.nodes, vec!["A"]);
+
+        assert!(h.shortest_path("A", "X").is_none());
+
+        let path = fastobo_graphs::algo::shortest_path(&g, "A", "F", &is_a_part_of()).unwrap();
+        assert_eq!(path.nodes, vec!["A", "C", "F"]);
+    }
+}
+
+mod similarity {
+    use super::*;
+
+    use fastobo_graphs::algo::Measure;
+    use fastobo_graphs::algo::Similarity;
+
+    fn assert_close(x: f64, y: f64) {
+        assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+    }
+
+    #[test]
+    fn seco() {
+        let g = graph();
+        let sim = Similarity::seco(&g, &PredicateSet::is_a());
+        assert_close(sim.ic("A").unwrap(), 1.0);
+        assert_close(sim.ic("B").unwrap(), 2.0 / 3.0);
+        assert_close(sim.ic("D").unwrap(), 1.0 / 3.0);
+        assert_close(sim.ic("E").unwrap(), 1.0 - 6f64.ln() / 8f64.ln());
+        assert!(sim.ic("Z").is_none());
+        assert_eq!(sim.ancestors("B").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn zhou() {
+        // depths are only defined without the `X` and `Y` cycle
+        let mut g = graph();
+        g.nodes.retain(|n| n.id != "X" && n.id != "Y");
+        g.edges.retain(|e| e.sub != "X" && e.sub != "Y");
+        let sim = Similarity::zhou(&g, &PredicateSet::is_a(), 0.5).unwrap();
+        assert_close(sim.ic("A").unwrap(), 1.0);
+        assert_close(sim.ic("E").unwrap(), 0.0);
+        assert_close(
+            sim.ic("D").unwrap(),
+            0.5 * (1.0 - 4f64.ln() / 6f64.ln()) + 0.5 * 2f64.ln() / 4f64.ln(),
+        );
+    }
+
+    #[test]
+    fn zhou_cycle() {
+        let g = fastobo_graphs::from_str(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "edges": [
+                    {"sub": "X", "pred": "is_a", "obj": "Y"},
+                    {"sub": "Y", "pred": "is_a", "obj": "X"}
+                ]
+            }]}"#,
+        )
+        .unwrap()
+        .graphs
+        .remove(0);
+        let err = Similarity::zhou(&g, &PredicateSet::is_a(), 0.5).unwrap_err();
+        assert!(matches!(err, fastobo_graphs::error::Error::Cycle(_)));
+        assert!(Similarity::seco(&g, &PredicateSet::is_a())
+            .ic("X")
+            .is_some());
+    }
+
+    #[test]
+    fn corpus() {
+        let g = graph();
+        let counts = vec![("A", 1), ("B", 2), ("F", 1)];
+        let sim = Similarity::corpus(&g, &PredicateSet::is_a(), counts);
+        assert_close(sim.ic("E").unwrap(), 0.0);
+        assert_close(sim.ic("D").unwrap(), (4.0f64 / 3.0).ln());
+        assert_close(sim.ic("C").unwrap(), 4f64.ln());
+        assert!(sim.ic("X").is_none());
+        assert_close(sim.resnik("B", "C"), (4.0f64 / 3.0).ln());
+    }
+
+    #[test]
+    fn pairwise() {
+        let g = graph();
+        let sim = Similarity::seco(&g, &PredicateSet::is_a());
+        assert_eq!(sim.mica("B", "C").unwrap().0, "D");
+        assert_close(sim.resnik("B", "C"), 1.0 / 3.0);
+        assert_close(sim.lin("B", "C"), 0.5);
+        assert_close(sim.lin("B", "B"), 1.0);
+        assert_close(sim.jiang_conrath_distance("B", "C"), 2.0 / 3.0);
+        assert_close(sim.jiang_conrath("B", "C"), 0.6);
+        assert_close(sim.similarity(Measure::Lin, "B", "C"), 0.5);
+        assert_close(sim.resnik("A", "X"), 0.0);
+    }
+
+    #[test]
+    fn groupwise() {
+        let g = graph();
+        let sim = Similarity::seco(&g, &PredicateSet::is_a());
+        let ic = |id| sim.ic(id).unwrap();
+        assert_close(
+            sim.sim_gic(&["A"], &["F"]),
+            ic("E")
+                / ["A", "B", "C", "D", "E", "F"]
+                    .iter()
+                    .map(|&x| ic(x))
+                    .sum::<f64>(),
+        );
+        assert_close(sim.sim_gic(&["A"], &["A"]), 1.0);
+        assert_close(
+            sim.best_match_average(Measure::Resnik, &["B"], &["C"]),
+            1.0 / 3.0,
+        );
+        assert_close(
+            sim.best_match_average(Measure::Lin, &["B", "C"], &["C"]),
+            ((0.5 + 1.0) / 2.0 + 1.0) / 2.0,
+        );
+        assert_close(
+            sim.best_match_average(Measure::Lin, &["B"], &[] as &[&str]),
+            0.0,
+        );
+    }
+}
+
+mod topo {
+    use super::*;
+
+    use fastobo_graphs::algo::Depth;
+    use fastobo_graphs::error::Error;
+
+    fn acyclic() -> Graph {
+        let mut g = graph();
+        g.edges.retain(|e| e.sub != "X" && e.sub != "Y");
+        g.edges[0].obj = "E".to_string();
+        g
+    }
+
+    #[test]
+    fn roots_leaves() {
+        let g = graph();
+        assert_eq!(
+            fastobo_graphs::algo::roots(&g, &PredicateSet::is_a()),
+            vec!["E"]
+        );
+        assert_eq!(
+            fastobo_graphs::algo::leaves(&g, &PredicateSet::is_a()),
+            vec!["A", "F"]
+        );
+        assert_eq!(fastobo_graphs::algo::leaves(&g, &is_a_part_of()), vec!["A"]);
+    }
+
+    #[test]
+    fn isolated_class() {
+        let mut g = graph();
+        let mut node = g.nodes[0].clone();
+        node.id = "Z".to_string();
+        node.ty = Some(fastobo_graphs::model::NodeType::Class);
+        g.nodes.push(node);
+        let h = Hierarchy::new(&g, &PredicateSet::is_a());
+        assert_eq!(h.roots(), vec!["E", "Z"]);
+        assert_eq!(h.leaves(), vec!["A", "F", "Z"]);
+    }
+
+    #[test]
+    fn topological_sort() {
+        let g = acyclic();
+        let order = fastobo_graphs::algo::topological_sort(&g, &PredicateSet::is_a()).unwrap();
+        assert_eq!(order, vec!["E", "D", "B", "A", "C", "F"]);
+    }
+
+    #[test]
+    fn cycle() {
+        let g = graph();
+        match fastobo_graphs::algo::topological_sort(&g, &PredicateSet::is_a()) {
+            Err(Error::Cycle(ids)) => assert_eq!(ids, vec!["X", "Y"]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(fastobo_graphs::algo::depths(&g, &PredicateSet::is_a()).is_err());
+    }
+
+    #[test]
+    fn depths() {
+        let g = acyclic();
+        let depths = fastobo_graphs::algo::depths(&g, &is_a_part_of()).unwrap();
+        assert_eq!(depths["E"], Depth { min: 0, max: 0 });
+        assert_eq!(depths["D"], Depth { min: 1, max: 1 });
+        assert_eq!(depths["C"], Depth { min: 2, max: 2 });
+        assert_eq!(depths["A"], Depth { min: 1, max: 3 });
+    }
+}
+
+mod cycles {
+    use super::*;
+
+    use fastobo_graphs::constants::property::obo_in_owl;
+    use fastobo_graphs::model::BasicPropertyValue;
+    use fastobo_graphs::model::Edge;
+    use fastobo_graphs::model::Meta;
+
+    #[test]
+    fn cycles() {
+        let g = graph();
+        let cycles = fastobo_graphs::algo::cycles(&g, &PredicateSet::is_a(), &[]);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].nodes, vec![("X", Some("x")), ("Y", Some("y"))]);
+        assert_eq!(cycles[0].edges, vec![&g.edges[7], &g.edges[8]]);
+    }
+
+    #[test]
+    fn skip_marked() {
+        let mut g = graph();
+        let mut edge: Edge = g.edges[0].clone();
+        edge.sub = "E".to_string();
+        edge.obj = "A".to_string();
+        edge.meta = Some(Box::new(Meta {
+            basic_property_values: vec![BasicPropertyValue::new(
+                obo_in_owl::IS_INFERRED.to_string(),
+                "true".to_string(),
+            )],
+            ..Default::default()
+        }));
+        g.edges.push(edge);
+
+        let cycles = fastobo_graphs::algo::cycles(&g, &PredicateSet::is_a(), &[]);
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(
+            cycles[0].ids().collect::<Vec<_>>(),
+            vec!["A", "B", "C", "D", "E"]
+        );
+        assert_eq!(cycles[0].edges.len(), 6);
+
+        let skip = [obo_in_owl::IS_INFERRED];
+        let cycles = fastobo_graphs::algo::cycles(&g, &PredicateSet::is_a(), &skip);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].ids().collect::<Vec<_>>(), vec!["X", "Y"]);
+    }
+
+    #[test]
+    fn deep() {
+        let mut g = graph();
+        let template = g.edges[0].clone();
+        g.edges = (0..100_000)
+            .map(|i| {
+                let mut edge = template.clone();
+                edge.sub = format!("N{}", i);
+                edge.obj = format!("N{}", (i + 1) % 100_000);
+                edge
+            })
+            .collect();
+        let cycles = Hierarchy::new(&g, &PredicateSet::is_a()).cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 100_000);
+    }
+}
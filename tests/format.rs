@@ -0,0 +1,447 @@
+extern crate fastobo_graphs;
+
+use std::path::PathBuf;
+
+use fastobo_graphs::error::Error;
+
+const JSON: &str = r#"
+  {"graphs": [{"id": "http://purl.obolibrary.org/obo/test.owl", "nodes": [{"id": "A", "lbl": "a"}]}]}
+"#;
+
+const YAML: &str = "
+graphs:
+  - id: http://purl.obolibrary.org/obo/test.owl
+    nodes:
+      - id: A
+        lbl: a
+";
+
+fn tempfile(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("fastobo-graphs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+mod read {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let json = fastobo_graphs::from_str(JSON).unwrap();
+        let yaml = fastobo_graphs::from_str(YAML).unwrap();
+        assert_eq!(json, yaml);
+        assert_eq!(json, fastobo_graphs::from_json_str(JSON).unwrap());
+        assert_eq!(yaml, fastobo_graphs::from_yaml_str(YAML).unwrap());
+    }
+
+    #[test]
+    fn from_reader() {
+        let json = fastobo_graphs::from_reader(JSON.as_bytes()).unwrap();
+        let yaml = fastobo_graphs::from_reader(YAML.as_bytes()).unwrap();
+        assert_eq!(json, yaml);
+    }
+
+    #[test]
+    fn from_file() {
+        let expected = fastobo_graphs::from_json_str(JSON).unwrap();
+        for (name, content) in &[
+            ("graph.json", JSON),
+            ("graph.yaml", YAML),
+            ("graph.yml", YAML),
+            ("graph-json.txt", JSON),
+            ("graph-yaml.txt", YAML),
+        ] {
+            let path = tempfile(name, content);
+            assert_eq!(
+                fastobo_graphs::from_file(&path).unwrap(),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn yaml_flow_mapping() {
+        let flow =
+            "{graphs: [{id: http://purl.obolibrary.org/obo/test.owl, nodes: [{id: A, lbl: a}]}]}";
+        let expected = fastobo_graphs::from_json_str(JSON).unwrap();
+        assert_eq!(fastobo_graphs::from_str(flow).unwrap(), expected);
+        assert_eq!(
+            fastobo_graphs::from_reader(flow.as_bytes()).unwrap(),
+            expected
+        );
+        assert!(fastobo_graphs::from_str("{graphs: []}").is_ok());
+    }
+
+    #[test]
+    fn json_error() {
+        let err = fastobo_graphs::from_str(r#"{"graphs": [}"#).unwrap_err();
+        assert!(matches!(err, Error::JsonPathError { .. }));
+        let err = fastobo_graphs::from_reader(r#"  {"graphs": 1}"#.as_bytes()).unwrap_err();
+        assert!(matches!(err, Error::JsonPathError { .. }));
+    }
+
+    #[test]
+    fn error_path() {
+        let json = r#"{"graphs": [{"id": "g", "nodes": [
+            {"id": "A"},
+            {"id": "B", "meta": {"xrefs": [{"val": "X:1"}, {"val": 2}]}}
+        ]}]}"#;
+        match fastobo_graphs::from_str(json).unwrap_err() {
+            Error::JsonPathError { path, .. } => {
+                assert_eq!(path, "graphs[0].nodes[1].meta.xrefs[1].val")
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        let yaml = "graphs:\n  - id: g\n    nodes:\n      - id: A\n        type: NOPE\n";
+        match fastobo_graphs::from_yaml_str(yaml).unwrap_err() {
+            Error::YamlPathError { path, .. } => assert_eq!(path, "graphs[0].nodes[0].type"),
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn extension_overrides_content() {
+        let path = tempfile("yaml-content.json", YAML);
+        let err = fastobo_graphs::from_file(&path).unwrap_err();
+        assert!(matches!(err, Error::JsonPathError { .. }));
+    }
+}
+
+mod write {
+    use super::*;
+
+    #[test]
+    fn yaml() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let yaml = fastobo_graphs::to_yaml_string(&doc).unwrap();
+        assert!(yaml.starts_with("graphs:"));
+        assert_eq!(fastobo_graphs::from_str(&yaml).unwrap(), doc);
+
+        let mut buffer = Vec::new();
+        fastobo_graphs::to_yaml_writer(&mut buffer, &doc).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), yaml);
+    }
+
+    #[test]
+    fn pretty() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let pretty = fastobo_graphs::to_string_pretty(&doc).unwrap();
+        assert!(pretty.starts_with("{\n  \"graphs\": [\n    {\n"));
+        assert_eq!(fastobo_graphs::from_str(&pretty).unwrap(), doc);
+
+        let mut buffer = Vec::new();
+        fastobo_graphs::to_writer_indent(&mut buffer, &doc, "    ").unwrap();
+        let indented = String::from_utf8(buffer).unwrap();
+        assert!(indented.starts_with("{\n    \"graphs\": [\n        {\n"));
+        assert_eq!(fastobo_graphs::from_str(&indented).unwrap(), doc);
+    }
+
+    #[test]
+    fn to_file() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        for name in &["out.json", "out.yaml", "out.yml"] {
+            let path = tempfile(name, "");
+            fastobo_graphs::to_file(&path, &doc).unwrap();
+            let content = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(
+                content.starts_with('{'),
+                name.ends_with(".json"),
+                "{}",
+                name
+            );
+            assert_eq!(fastobo_graphs::from_file(&path).unwrap(), doc);
+        }
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+mod compression {
+    use super::*;
+
+    fn roundtrip(name: &str, magic: &[u8]) {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let path = tempfile(name, "");
+        fastobo_graphs::to_file(&path, &doc).unwrap();
+        assert!(std::fs::read(&path).unwrap().starts_with(magic));
+        assert_eq!(fastobo_graphs::from_file(&path).unwrap(), doc);
+
+        // compressed files are detected from their content as well
+        let renamed = path.with_extension("bin");
+        std::fs::rename(&path, &renamed).unwrap();
+        assert_eq!(fastobo_graphs::from_file(&renamed).unwrap(), doc);
+    }
+
+    #[test]
+    #[cfg(feature = "gzip")]
+    fn gzip() {
+        roundtrip("graph.json.gz", &[0x1f, 0x8b]);
+        roundtrip("graph.yaml.gz", &[0x1f, 0x8b]);
+    }
+
+    #[test]
+    #[cfg(feature = "zstd")]
+    fn zstd() {
+        roundtrip("graph.json.zst", &[0x28, 0xb5, 0x2f, 0xfd]);
+        roundtrip("graph.yml.zst", &[0x28, 0xb5, 0x2f, 0xfd]);
+    }
+}
+
+#[cfg(not(all(feature = "gzip", feature = "zstd")))]
+mod unsupported_compression {
+    use super::*;
+
+    fn check(name: &str, feature: &str) {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let path = tempfile(name, JSON);
+        let err = fastobo_graphs::from_file(&path).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedCompression(f) if f == feature));
+
+        std::fs::remove_file(&path).unwrap();
+        let err = fastobo_graphs::to_file(&path, &doc).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedCompression(f) if f == feature));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    #[cfg(not(feature = "gzip"))]
+    fn gzip() {
+        check("graph.json.gz", "gzip");
+    }
+
+    #[test]
+    #[cfg(not(feature = "zstd"))]
+    fn zstd() {
+        check("graph.json.zst", "zstd");
+    }
+}
+
+mod verbose {
+    use super::*;
+
+    use fastobo_graphs::model::GraphDocument;
+    use fastobo_graphs::model::Verbose;
+
+    #[test]
+    fn compact() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let json = fastobo_graphs::to_string(&doc).unwrap();
+        assert_eq!(
+            json,
+            r#"{"graphs":[{"nodes":[{"id":"A","lbl":"a"}],"id":"http://purl.obolibrary.org/obo/test.owl"}]}"#
+        );
+        assert_eq!(fastobo_graphs::from_str(&json).unwrap(), doc);
+    }
+
+    #[test]
+    fn verbose() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let json = serde_json::to_string(&Verbose(&doc)).unwrap();
+        assert!(json.contains(r#""edges":[]"#));
+        assert!(json.contains(r#""meta":null"#));
+        assert_eq!(fastobo_graphs::from_str(&json).unwrap(), doc);
+    }
+
+    #[test]
+    fn deprecated() {
+        let doc: GraphDocument = fastobo_graphs::from_str(
+            r#"{"graphs": [{"id": "test", "meta": {"deprecated": false}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fastobo_graphs::to_string(&doc).unwrap(),
+            r#"{"graphs":[{"id":"test","meta":{}}]}"#
+        );
+        let verbose = serde_json::to_string(&Verbose(&doc)).unwrap();
+        assert!(verbose.contains(r#""deprecated":false"#));
+    }
+
+    #[test]
+    fn to_writer_verbose() {
+        let doc = fastobo_graphs::from_str(JSON).unwrap();
+        let mut json = Vec::new();
+        fastobo_graphs::to_writer_verbose(&mut json, &doc).unwrap();
+        let expected = serde_json::to_string(&Verbose(&doc)).unwrap();
+        assert_eq!(String::from_utf8(json).unwrap(), expected);
+        assert_eq!(fastobo_graphs::to_string_verbose(&doc).unwrap(), expected);
+
+        let path = tempfile("verbose.json", "");
+        fastobo_graphs::to_file_verbose(&path, &doc).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    }
+}
+
+mod extra {
+    use super::*;
+
+    use fastobo_graphs::model::GraphDocument;
+    use fastobo_graphs::model::Strict;
+
+    const EXTRA: &str = r#"{
+        "graphs": [{
+            "id": "test",
+            "x-graph": {"nested": [1, 2]},
+            "nodes": [{
+                "id": "A",
+                "x-node": true,
+                "meta": {"x-meta": "value", "synonyms": [{"pred": "hasExactSynonym", "val": "a", "synonymType": "ABBREV"}]}
+            }],
+            "edges": [{"sub": "A", "pred": "is_a", "obj": "B", "x-edge": null}],
+            "logicalDefinitionAxioms": [{
+                "definedClassId": "A",
+                "genusIds": ["B"],
+                "restrictions": [{"propertyId": "R", "fillerId": "C", "x-restriction": 1}],
+                "x-axiom": "value"
+            }]
+        }],
+        "x-document": "value"
+    }"#;
+
+    #[test]
+    fn roundtrip() {
+        let doc = fastobo_graphs::from_str(EXTRA).unwrap();
+        assert_eq!(doc.extra["x-document"], "value");
+        assert_eq!(doc.graphs[0].extra["x-graph"]["nested"][1], 2);
+        assert_eq!(doc.graphs[0].nodes[0].extra["x-node"], true);
+        let meta = doc.graphs[0].nodes[0].meta.as_ref().unwrap();
+        assert_eq!(meta.extra["x-meta"], "value");
+        assert_eq!(meta.synonyms[0].extra["synonymType"], "ABBREV");
+        assert!(doc.graphs[0].edges[0].extra["x-edge"].is_null());
+
+        let json = fastobo_graphs::to_string(&doc).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(EXTRA).unwrap();
+        let actual: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual, expected);
+
+        let yaml = fastobo_graphs::to_yaml_string(&doc).unwrap();
+        assert_eq!(fastobo_graphs::from_str(&yaml).unwrap(), doc);
+    }
+
+    #[test]
+    fn strict() {
+        let err = serde_json::from_str::<Strict<GraphDocument>>(EXTRA).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown field `x-graph`"),
+            "{}",
+            err
+        );
+        let doc = serde_json::from_str::<Strict<GraphDocument>>(JSON)
+            .unwrap()
+            .0;
+        assert_eq!(doc, fastobo_graphs::from_str(JSON).unwrap());
+    }
+
+    #[test]
+    fn strict_nested() {
+        let json = r#"{"graphs": [{"id": "test", "nodes": [{"id": "A", "meta": {
+            "synonyms": [{"pred": "hasExactSynonym", "val": "a", "synonymType": "ABBREV"}]
+        }}]}]}"#;
+        match fastobo_graphs::from_str_strict(json).unwrap_err() {
+            Error::JsonPathError { path, error } => {
+                assert_eq!(path, "graphs[0].nodes[0].meta.synonyms[0]");
+                assert!(error.to_string().contains("unknown field `synonymType`"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+        assert!(fastobo_graphs::from_str(json).is_ok());
+    }
+
+    #[test]
+    fn from_reader_strict() {
+        assert!(fastobo_graphs::from_reader_strict(EXTRA.as_bytes()).is_err());
+        let doc = fastobo_graphs::from_reader_strict(JSON.as_bytes()).unwrap();
+        assert_eq!(doc, fastobo_graphs::from_str(JSON).unwrap());
+
+        let path = tempfile("strict.yaml", YAML);
+        assert_eq!(fastobo_graphs::from_file_strict(&path).unwrap(), doc);
+    }
+}
+
+mod element {
+    use super::*;
+
+    use fastobo_graphs::model::GraphDocument;
+    use fastobo_graphs::model::Strict;
+
+    #[test]
+    fn duplicate_field() {
+        let json = r#"{"graphs": [{"id": "a", "id": "b"}]}"#;
+        let err = fastobo_graphs::from_str(json).unwrap_err();
+        assert!(err.to_string().contains("duplicate field `id`"), "{}", err);
+    }
+
+    #[test]
+    fn missing_field() {
+        let json = r#"{"graphs": [{"id": "test", "edges": [{"sub": "A", "obj": "B"}]}]}"#;
+        match fastobo_graphs::from_str(json).unwrap_err() {
+            Error::JsonPathError { path, error } => {
+                assert_eq!(path, "graphs[0].edges[0]");
+                assert!(error.to_string().contains("missing field `pred`"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn null_fields() {
+        let json = r#"{"graphs": [{
+            "id": "test",
+            "meta": null,
+            "nodes": [{"id": "A", "lbl": null, "type": null}],
+            "logicalDefinitionAxioms": [{
+                "definedClassId": "A",
+                "genusIds": ["B"],
+                "restrictions": [null, {"propertyId": "R", "fillerId": "C"}, null]
+            }]
+        }]}"#;
+        let doc = fastobo_graphs::from_str(json).unwrap();
+        let graph = &doc.graphs[0];
+        assert!(graph.meta.is_none());
+        assert!(graph.nodes[0].label.is_none());
+        assert!(graph.nodes[0].ty.is_none());
+        let restrictions = &graph.logical_definition_axioms[0].restrictions;
+        assert_eq!(restrictions.len(), 1);
+        assert_eq!(restrictions[0].filler_id, "C");
+    }
+
+    #[test]
+    fn extra_order() {
+        let json = r#"{"graphs":[{"id":"test","x-b":1,"x-a":2}]}"#;
+        let doc = fastobo_graphs::from_str(json).unwrap();
+        assert_eq!(
+            fastobo_graphs::to_string(&doc).unwrap(),
+            r#"{"graphs":[{"id":"test","x-a":2,"x-b":1}]}"#
+        );
+    }
+
+    #[test]
+    fn strict_restriction() {
+        let json = r#"{"graphs": [{"id": "test", "logicalDefinitionAxioms": [{
+            "definedClassId": "A",
+            "genusIds": ["B"],
+            "restrictions": [null, {"propertyId": "R", "fillerId": "C", "x-restriction": 1}]
+        }]}]}"#;
+        match fastobo_graphs::from_str_strict(json).unwrap_err() {
+            Error::JsonPathError { path, error } => {
+                assert_eq!(path, "graphs[0].logicalDefinitionAxioms[0].restrictions[1]");
+                assert!(error.to_string().contains("unknown field `x-restriction`"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn strict_yaml() {
+        let yaml = "graphs:\n  - id: test\n    x-graph: 1\n";
+        let err = serde_yaml::from_str::<Strict<GraphDocument>>(yaml).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown field `x-graph`"),
+            "{}",
+            err
+        );
+        assert!(fastobo_graphs::from_str(yaml).is_ok());
+    }
+}
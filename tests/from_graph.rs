@@ -0,0 +1,379 @@
+extern crate fastobo;
+extern crate fastobo_graphs;
+
+use fastobo::ast::EntityFrame;
+use fastobo::ast::OboDoc;
+use fastobo_graphs::from_graph::Config;
+use fastobo_graphs::from_graph::Context;
+use fastobo_graphs::from_graph::Diagnostic;
+use fastobo_graphs::from_graph::TypeInference;
+use fastobo_graphs::model::Graph;
+use fastobo_graphs::model::NodeType;
+use fastobo_graphs::FromGraph;
+use fastobo_graphs::FromGraphCtx;
+
+fn graph(json: &str) -> Graph {
+    let doc = fastobo_graphs::from_str(json).unwrap();
+    doc.graphs.into_iter().next().unwrap()
+}
+
+mod type_inference {
+    use super::*;
+
+    const UNTYPED: &str = r#"{"graphs": [{
+        "id": "http://purl.obolibrary.org/obo/test.owl",
+        "nodes": [
+            {"id": "http://purl.obolibrary.org/obo/TEST_0000001", "lbl": "a"},
+            {"id": "http://purl.obolibrary.org/obo/TEST_0000002", "lbl": "b"},
+            {"id": "http://purl.obolibrary.org/obo/BFO_0000050", "lbl": "part of"}
+        ],
+        "edges": [{
+            "sub": "http://purl.obolibrary.org/obo/TEST_0000001",
+            "pred": "http://purl.obolibrary.org/obo/BFO_0000050",
+            "obj": "http://purl.obolibrary.org/obo/TEST_0000002"
+        }]
+    }]}"#;
+
+    #[test]
+    fn skip_by_default() {
+        let mut ctx = Context::default();
+        let doc = OboDoc::from_graph_ctx(graph(UNTYPED), &mut ctx).unwrap();
+        assert!(doc.entities().is_empty());
+        let untyped = ctx
+            .diagnostics
+            .iter()
+            .filter(|d| matches!(d, Diagnostic::UntypedNode { .. }));
+        assert_eq!(untyped.count(), 3);
+        assert_eq!(OboDoc::from_graph(graph(UNTYPED)).unwrap(), doc);
+    }
+
+    #[test]
+    fn default_type() {
+        let config = Config {
+            type_inference: vec![TypeInference::Default(NodeType::Class)],
+            ..Default::default()
+        };
+        let mut ctx = Context::new(config);
+        let doc = OboDoc::from_graph_ctx(graph(UNTYPED), &mut ctx).unwrap();
+        assert_eq!(doc.entities().len(), 3);
+        assert!(doc.entities().iter().all(|e| e.as_term().is_some()));
+    }
+
+    #[test]
+    fn edges_then_default() {
+        let config = Config {
+            type_inference: vec![
+                TypeInference::Edges,
+                TypeInference::Default(NodeType::Class),
+            ],
+            ..Default::default()
+        };
+        let mut ctx = Context::new(config);
+        let doc = OboDoc::from_graph_ctx(graph(UNTYPED), &mut ctx).unwrap();
+        assert_eq!(
+            doc.entities()
+                .iter()
+                .filter(|e| e.as_term().is_some())
+                .count(),
+            2
+        );
+        assert_eq!(
+            doc.entities()
+                .iter()
+                .filter(|e| e.as_typedef().is_some())
+                .count(),
+            1
+        );
+        assert!(ctx.diagnostics.contains(&Diagnostic::InferredType {
+            id: String::from("http://purl.obolibrary.org/obo/BFO_0000050"),
+            ty: NodeType::Property,
+            strategy: TypeInference::Edges,
+        }));
+        assert!(ctx.diagnostics.contains(&Diagnostic::InferredType {
+            id: String::from("http://purl.obolibrary.org/obo/TEST_0000001"),
+            ty: NodeType::Class,
+            strategy: TypeInference::Default(NodeType::Class),
+        }));
+    }
+
+    #[test]
+    fn axioms() {
+        let g = graph(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000001"},
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000002"}
+                ],
+                "domainRangeAxioms": [{
+                    "predicateId": "http://purl.obolibrary.org/obo/TEST_0000002",
+                    "domainClassIds": ["http://purl.obolibrary.org/obo/TEST_0000001"]
+                }]
+            }]}"#,
+        );
+        let config = Config {
+            type_inference: vec![TypeInference::Axioms],
+            ..Default::default()
+        };
+        let mut ctx = Context::new(config);
+        let doc = OboDoc::from_graph_ctx(g, &mut ctx).unwrap();
+        assert_eq!(doc.entities().len(), 2);
+        assert!(doc.entities().iter().any(|e| e.as_term().is_some()));
+        assert!(doc.entities().iter().any(|e| e.as_typedef().is_some()));
+    }
+
+    #[test]
+    fn class_with_rdf_type() {
+        let g = graph(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000001", "type": "CLASS"},
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000002", "type": "CLASS"}
+                ],
+                "edges": [{
+                    "sub": "http://purl.obolibrary.org/obo/TEST_0000001",
+                    "pred": "rdf:type",
+                    "obj": "http://purl.obolibrary.org/obo/TEST_0000002"
+                }]
+            }]}"#,
+        );
+        let doc = OboDoc::from_graph(g).unwrap();
+        let term = doc
+            .entities()
+            .iter()
+            .find_map(|e| e.as_term())
+            .filter(|t| t.id().as_ref().to_string() == "TEST:0000001")
+            .unwrap();
+        assert_eq!(term.clauses().len(), 1);
+        assert_eq!(
+            term.clauses()[0].as_inner().to_string(),
+            "relationship: rdf:type TEST:0000002"
+        );
+    }
+}
+
+mod dangling_edges {
+    use super::*;
+    use fastobo_graphs::from_graph::DanglingEdges;
+
+    const DANGLING: &str = r#"{"graphs": [{
+        "id": "http://purl.obolibrary.org/obo/test.owl",
+        "nodes": [
+            {"id": "http://purl.obolibrary.org/obo/TEST_0000001", "type": "CLASS"}
+        ],
+        "edges": [
+            {
+                "sub": "http://purl.obolibrary.org/obo/TEST_0000001",
+                "pred": "is_a",
+                "obj": "http://purl.obolibrary.org/obo/EXT_0000001"
+            },
+            {
+                "sub": "http://purl.obolibrary.org/obo/EXT_0000002",
+                "pred": "is_a",
+                "obj": "http://purl.obolibrary.org/obo/TEST_0000001"
+            }
+        ]
+    }]}"#;
+
+    fn context(dangling_edges: DanglingEdges) -> Context {
+        Context::new(Config {
+            dangling_edges,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn keep() {
+        let mut ctx = context(DanglingEdges::Keep);
+        let doc = OboDoc::from_graph_ctx(graph(DANGLING), &mut ctx).unwrap();
+        assert_eq!(doc.entities().len(), 1);
+        assert_eq!(doc.entities()[0].as_term().unwrap().len(), 1);
+        assert_eq!(
+            ctx.diagnostics,
+            vec![Diagnostic::DanglingEdge {
+                sub: String::from("http://purl.obolibrary.org/obo/EXT_0000002"),
+                pred: String::from("is_a"),
+                obj: String::from("http://purl.obolibrary.org/obo/TEST_0000001"),
+            }]
+        );
+    }
+
+    #[test]
+    fn drop() {
+        let mut ctx = context(DanglingEdges::Drop);
+        let doc = OboDoc::from_graph_ctx(graph(DANGLING), &mut ctx).unwrap();
+        assert_eq!(doc.entities().len(), 1);
+        assert!(doc.entities()[0].as_term().unwrap().is_empty());
+        assert_eq!(ctx.diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn stub() {
+        let mut ctx = context(DanglingEdges::Stub);
+        let doc = OboDoc::from_graph_ctx(graph(DANGLING), &mut ctx).unwrap();
+        assert_eq!(doc.entities().len(), 3);
+        assert!(doc.entities().iter().all(|e| e.as_term().is_some()));
+        assert!(ctx
+            .diagnostics
+            .iter()
+            .all(|d| matches!(d, Diagnostic::StubFrame { .. })));
+    }
+
+    #[test]
+    fn stub_predicate() {
+        let g = graph(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000001", "type": "CLASS"},
+                    {"id": "http://purl.obolibrary.org/obo/TEST_0000002", "type": "CLASS"},
+                    {"id": "http://purl.obolibrary.org/obo/RO_0000001", "type": "PROPERTY"}
+                ],
+                "edges": [
+                    {
+                        "sub": "http://purl.obolibrary.org/obo/TEST_0000001",
+                        "pred": "http://purl.obolibrary.org/obo/BFO_0000050",
+                        "obj": "http://purl.obolibrary.org/obo/TEST_0000002"
+                    },
+                    {
+                        "sub": "http://purl.obolibrary.org/obo/TEST_0000001",
+                        "pred": "http://purl.obolibrary.org/obo/RO_0000001",
+                        "obj": "http://purl.obolibrary.org/obo/TEST_0000002"
+                    },
+                    {
+                        "sub": "http://purl.obolibrary.org/obo/TEST_0000002",
+                        "pred": "is_a",
+                        "obj": "http://purl.obolibrary.org/obo/TEST_0000001"
+                    }
+                ]
+            }]}"#,
+        );
+        let mut ctx = context(DanglingEdges::Stub);
+        let doc = OboDoc::from_graph_ctx(g, &mut ctx).unwrap();
+        let typedefs = doc
+            .entities()
+            .iter()
+            .filter_map(|e| e.as_typedef())
+            .map(|f| f.id().as_inner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(typedefs, vec!["BFO:0000050", "RO:0000001"]);
+        assert_eq!(
+            ctx.diagnostics,
+            vec![Diagnostic::StubFrame {
+                id: String::from("http://purl.obolibrary.org/obo/BFO_0000050"),
+            }]
+        );
+    }
+
+    #[test]
+    fn error() {
+        let mut ctx = context(DanglingEdges::Error);
+        let err = OboDoc::from_graph_ctx(graph(DANGLING), &mut ctx).unwrap_err();
+        assert!(matches!(
+            err,
+            fastobo_graphs::error::Error::DanglingEdge(ref id)
+                if id == "http://purl.obolibrary.org/obo/EXT_0000001"
+        ));
+    }
+}
+
+mod errors {
+    use super::*;
+
+    use fastobo_graphs::error::Error;
+
+    #[test]
+    fn invalid_identifier() {
+        let g = graph(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [{
+                    "id": "http://purl.obolibrary.org/obo/TEST_0000001",
+                    "type": "CLASS",
+                    "meta": {"xrefs": [{"val": "not an id"}]}
+                }]
+            }]}"#,
+        );
+        match OboDoc::from_graph(g).unwrap_err() {
+            Error::EntityError { id, error } => {
+                assert_eq!(id, "http://purl.obolibrary.org/obo/TEST_0000001");
+                assert!(matches!(
+                    *error,
+                    Error::InvalidIdentifier { ref context, ref value, .. }
+                        if context == "xref" && value == "not an id"
+                ));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn edge_subject_mismatch() {
+        let g = graph(
+            r#"{"graphs": [{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [{"id": "http://purl.obolibrary.org/obo/TEST_0000001", "type": "CLASS"}],
+                "edges": [{
+                    "sub": "http://purl.obolibrary.org/obo/TEST_0000002",
+                    "pred": "is_a",
+                    "obj": "http://purl.obolibrary.org/obo/TEST_0000003"
+                }]
+            }]}"#,
+        );
+        let node = g.nodes.into_iter().next().unwrap();
+        match <Option<EntityFrame>>::from_graph((node, g.edges)).unwrap_err() {
+            Error::EdgeSubjectMismatch { node, edge } => {
+                assert_eq!(node, "http://purl.obolibrary.org/obo/TEST_0000001");
+                assert_eq!(edge, "http://purl.obolibrary.org/obo/TEST_0000002");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+}
+
+mod shorthand {
+    use super::*;
+
+    use fastobo::ast::TypedefClause;
+
+    fn typedef(val_type: &str) -> fastobo::ast::TypedefFrame {
+        let g = graph(&format!(
+            r#"{{"graphs": [{{
+                "id": "http://purl.obolibrary.org/obo/test.owl",
+                "nodes": [{{
+                    "id": "http://purl.obolibrary.org/obo/BFO_0000050",
+                    "type": "PROPERTY",
+                    "meta": {{"basicPropertyValues": [{{
+                        "pred": "http://www.geneontology.org/formats/oboInOwl#shorthand",
+                        "val": "part_of"{}
+                    }}]}}
+                }}]
+            }}]}}"#,
+            val_type
+        ));
+        let node = g.nodes.into_iter().next().unwrap();
+        match <Option<EntityFrame>>::from_graph(node).unwrap() {
+            Some(EntityFrame::Typedef(frame)) => *frame,
+            other => panic!("unexpected frame: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn typed() {
+        let frame = typedef(r#", "valType": "http://www.w3.org/2001/XMLSchema#string""#);
+        assert_eq!(frame.id().as_inner().to_string(), "part_of");
+        assert!(!frame
+            .iter()
+            .any(|c| matches!(c.as_inner(), TypedefClause::PropertyValue(_))));
+    }
+
+    #[test]
+    fn untyped() {
+        let frame = typedef("");
+        assert_eq!(frame.id().as_inner().to_string(), "part_of");
+        assert!(!frame
+            .iter()
+            .any(|c| matches!(c.as_inner(), TypedefClause::PropertyValue(_))));
+    }
+}
@@ -0,0 +1,102 @@
+extern crate fastobo_graphs;
+
+use fastobo_graphs::index::IndexedGraph;
+use fastobo_graphs::model::Edge;
+use fastobo_graphs::model::Graph;
+use fastobo_graphs::model::Node;
+
+const JSON: &str = r#"{"graphs": [{
+    "id": "http://purl.obolibrary.org/obo/test.owl",
+    "nodes": [
+        {"id": "TEST:1", "lbl": "a", "meta": {"xrefs": [{"val": "X:1"}, {"val": "X:1"}]}},
+        {"id": "TEST:2", "lbl": "b", "meta": {"xrefs": [{"val": "X:1"}]}},
+        {"id": "TEST:3", "lbl": "c"},
+        {"id": "TEST:1", "lbl": "duplicate"}
+    ],
+    "edges": [
+        {"sub": "TEST:1", "pred": "is_a", "obj": "TEST:2"},
+        {"sub": "TEST:1", "pred": "BFO:0000050", "obj": "TEST:3"},
+        {"sub": "TEST:2", "pred": "is_a", "obj": "TEST:3"}
+    ]
+}]}"#;
+
+fn graph() -> Graph {
+    let doc = fastobo_graphs::from_str(JSON).unwrap();
+    doc.graphs.into_iter().next().unwrap()
+}
+
+fn triples<'a>(edges: impl Iterator<Item = &'a Edge>) -> Vec<(&'a str, &'a str, &'a str)> {
+    edges
+        .map(|e| (e.sub.as_str(), e.pred.as_str(), e.obj.as_str()))
+        .collect()
+}
+
+#[test]
+fn nodes() {
+    let graph = graph();
+    let index = IndexedGraph::new(&graph);
+    assert!(index.contains_node("TEST:3"));
+    assert!(!index.contains_node("TEST:4"));
+    assert_eq!(index.node("TEST:1").unwrap().label.as_deref(), Some("a"));
+    assert_eq!(index.node("TEST:2").unwrap().label.as_deref(), Some("b"));
+    assert!(index.node("TEST:4").is_none());
+}
+
+#[test]
+fn edges() {
+    let graph = graph();
+    let index = IndexedGraph::new(&graph);
+    assert_eq!(
+        triples(index.outgoing("TEST:1")),
+        vec![
+            ("TEST:1", "is_a", "TEST:2"),
+            ("TEST:1", "BFO:0000050", "TEST:3")
+        ]
+    );
+    assert_eq!(
+        triples(index.incoming("TEST:3")),
+        vec![
+            ("TEST:1", "BFO:0000050", "TEST:3"),
+            ("TEST:2", "is_a", "TEST:3")
+        ]
+    );
+    assert_eq!(
+        triples(index.edges_with_predicate("is_a")),
+        vec![("TEST:1", "is_a", "TEST:2"), ("TEST:2", "is_a", "TEST:3")]
+    );
+    assert_eq!(index.outgoing("TEST:3").count(), 0);
+    assert_eq!(index.incoming("TEST:4").count(), 0);
+}
+
+#[test]
+fn xrefs() {
+    let graph = graph();
+    let index = IndexedGraph::new(&graph);
+    let ids = index
+        .nodes_with_xref("X:1")
+        .map(|n| n.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["TEST:1", "TEST:2"]);
+    assert_eq!(index.nodes_with_xref("X:2").count(), 0);
+}
+
+#[test]
+fn rebuild() {
+    let mut graph = graph();
+    assert!(!IndexedGraph::new(&graph).contains_node("TEST:4"));
+
+    graph.nodes.push(Node {
+        id: "TEST:4".to_string(),
+        meta: None,
+        ty: None,
+        label: None,
+        property_type: None,
+        extra: Default::default(),
+    });
+    graph.edges.clear();
+
+    let index = IndexedGraph::new(&graph);
+    assert!(index.contains_node("TEST:4"));
+    assert_eq!(index.outgoing("TEST:1").count(), 0);
+    assert_eq!(index.graph().nodes.len(), 5);
+}
@@ -0,0 +1,516 @@
+extern crate fastobo;
+extern crate fastobo_graphs;
+
+use std::str::FromStr;
+
+use fastobo::ast::OboDoc;
+use fastobo_graphs::model::Graph;
+use fastobo_graphs::FromGraph;
+use fastobo_graphs::IntoGraph;
+
+fn graph(obo: &str) -> Graph {
+    let doc = OboDoc::from_str(obo).unwrap();
+    doc.into_graph().unwrap().graphs.into_iter().next().unwrap()
+}
+
+mod property_value {
+    use super::*;
+
+    const OBO: &str = "ontology: test
+
+[Term]
+id: TEST:001
+property_value: IAO:0000001 \"42\" xsd:integer
+property_value: IAO:0000002 \"true\" xsd:boolean
+property_value: IAO:0000003 \"GO:0000001\" xsd:string
+property_value: IAO:0000004 TEST:002
+";
+
+    #[test]
+    fn datatype() {
+        let g = graph(OBO);
+        let meta = g.nodes[0].meta.as_ref().unwrap();
+        let types = meta
+            .basic_property_values
+            .iter()
+            .map(|pv| pv.val_type.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                Some("http://www.w3.org/2001/XMLSchema#integer"),
+                Some("http://www.w3.org/2001/XMLSchema#boolean"),
+                Some("http://www.w3.org/2001/XMLSchema#string"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let doc = OboDoc::from_graph(graph(OBO)).unwrap();
+        let text = doc.to_string();
+        assert!(text.contains("property_value: IAO:0000001 \"42\" xsd:integer"));
+        assert!(text.contains("property_value: IAO:0000002 \"true\" xsd:boolean"));
+        assert!(text.contains("property_value: IAO:0000003 \"GO:0000001\" xsd:string"));
+        assert!(text.contains("property_value: IAO:0000004 TEST:002"));
+    }
+}
+
+mod definition {
+    use super::*;
+
+    const OBO: &str = "ontology: test
+
+[Term]
+id: TEST:001
+def: \"A test term.\" [PMID:123 \"Some paper\", ISBN:456] {source=\"TEST:002\"}
+";
+
+    #[test]
+    fn meta() {
+        let g = graph(OBO);
+        let def = g.nodes[0]
+            .meta
+            .as_ref()
+            .and_then(|m| m.definition.as_ref())
+            .unwrap();
+        assert_eq!(def.xrefs.len(), 2);
+        let meta = def.meta.as_ref().unwrap();
+        assert_eq!(meta.xrefs.len(), 1);
+        assert_eq!(meta.xrefs[0].val, def.xrefs[0]);
+        assert_eq!(meta.xrefs[0].label.as_deref(), Some("Some paper"));
+        assert_eq!(meta.basic_property_values.len(), 1);
+        assert_eq!(meta.basic_property_values[0].val, "TEST:002");
+    }
+
+    #[test]
+    fn roundtrip() {
+        let doc = OboDoc::from_graph(graph(OBO)).unwrap();
+        let expected = OboDoc::from_str(OBO).unwrap();
+        assert_eq!(
+            doc.entities()[0].definition().unwrap(),
+            expected.entities()[0].definition().unwrap()
+        );
+        assert!(doc.to_string().contains(
+            "def: \"A test term.\" [PMID:123 \"Some paper\", ISBN:456] {source=\"TEST:002\"}"
+        ));
+    }
+}
+
+mod frame {
+    use super::*;
+
+    use fastobo::ast::EntityFrame;
+    use fastobo::ast::HeaderFrame;
+    use fastobo::ast::TermFrame;
+    use fastobo_graphs::into_graph::Context;
+    use fastobo_graphs::model::Meta;
+    use fastobo_graphs::IntoGraphCtx;
+
+    const OBO: &str = "ontology: test
+data-version: 2020-01-01
+
+[Term]
+id: TEST:001
+name: first term
+is_a: TEST:002
+relationship: part_of TEST:003
+";
+
+    #[test]
+    fn term_frame() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let mut ctx = Context::from(doc.header());
+        let frame: TermFrame = doc.entities()[0].as_term().unwrap().clone();
+        let g: Graph = frame.into_graph_ctx(&mut ctx).unwrap();
+        assert_eq!(g.nodes.len(), 1);
+        assert_eq!(g.nodes[0].id, "http://purl.obolibrary.org/obo/TEST_001");
+        assert_eq!(g.nodes[0].label.as_deref(), Some("first term"));
+        assert_eq!(g.edges.len(), 2);
+        assert!(g.edges.iter().all(|e| e.sub == g.nodes[0].id));
+    }
+
+    #[test]
+    fn header_frame() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let mut ctx = Context::from(doc.header());
+        let header: HeaderFrame = doc.header().clone();
+        let meta: Meta = header.into_graph_ctx(&mut ctx).unwrap();
+        assert_eq!(
+            meta.version.as_deref(),
+            Some("http://purl.obolibrary.org/obo/test/2020-01-01/test.owl")
+        );
+    }
+
+    #[test]
+    fn node_and_edges() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let mut ctx = Context::from(doc.header());
+        let entity = doc.entities()[0].clone();
+        let g: Graph = entity.into_graph_ctx(&mut ctx).unwrap();
+
+        let node = g.nodes.into_iter().next().unwrap();
+        let frame = <Option<EntityFrame>>::from_graph((node, g.edges)).unwrap();
+        let frame = frame.unwrap();
+        let term = frame.as_term().unwrap();
+        assert_eq!(term.id().as_inner().to_string(), "TEST:001");
+        let clauses = term
+            .iter()
+            .map(|c| c.as_inner().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(clauses[..2], ["name: first term", "is_a: TEST:002"]);
+        assert!(clauses[2].starts_with("relationship: ") && clauses[2].ends_with(" TEST:003"));
+
+        // identifiers are compacted like in the document conversion
+        let doc = OboDoc::from_graph(graph(OBO)).unwrap();
+        assert_eq!(frame, doc.entities()[0]);
+    }
+}
+
+mod borrowed {
+    use super::*;
+
+    use fastobo_graphs::into_graph::Context;
+    use fastobo_graphs::IntoGraphCtx;
+
+    const OBO: &str = "format-version: 1.4
+ontology: test
+treat-xrefs-as-is_a: EXT
+treat-xrefs-as-relationship: UBERON part_of
+
+[Term]
+id: TEST:001
+name: first term
+xref: EXT:001
+xref: UBERON:001
+property_value: IAO:0000001 \"42\" xsd:integer
+
+[Typedef]
+id: part_of
+name: part of
+";
+
+    /// Get the edges of a graph as sorted `(sub, pred, obj)` triples.
+    fn triples(g: &Graph) -> Vec<(&str, &str, &str)> {
+        let mut triples = g
+            .edges
+            .iter()
+            .map(|e| (e.sub.as_str(), e.pred.as_str(), e.obj.as_str()))
+            .collect::<Vec<_>>();
+        triples.sort_unstable();
+        triples
+    }
+
+    #[test]
+    fn document() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let expected = vec![
+            (
+                "http://purl.obolibrary.org/obo/TEST_001",
+                "http://purl.obolibrary.org/obo/test.owl#part_of",
+                "http://purl.obolibrary.org/obo/UBERON_001",
+            ),
+            (
+                "http://purl.obolibrary.org/obo/TEST_001",
+                "is_a",
+                "http://purl.obolibrary.org/obo/EXT_001",
+            ),
+        ];
+        let nodes = vec![
+            "http://purl.obolibrary.org/obo/TEST_001",
+            "http://purl.obolibrary.org/obo/test.owl#part_of",
+        ];
+
+        let borrowed = (&doc).into_graph().unwrap().graphs.remove(0);
+        assert_eq!(triples(&borrowed), expected);
+        assert_eq!(
+            borrowed.nodes.iter().map(|n| &n.id).collect::<Vec<_>>(),
+            nodes
+        );
+
+        let owned = doc.into_graph().unwrap().graphs.remove(0);
+        assert_eq!(triples(&owned), expected);
+        assert_eq!(owned.nodes.iter().map(|n| &n.id).collect::<Vec<_>>(), nodes);
+    }
+
+    #[test]
+    fn same_as_fastobo() {
+        // owned documents are preprocessed with `OboDoc::treat_xrefs`
+        let doc = OboDoc::from_str(
+            "format-version: 1.4
+ontology: test
+treat-xrefs-as-is_a: EXT
+treat-xrefs-as-has-subclass: TEST
+treat-xrefs-as-relationship: UBERON part_of
+treat-xrefs-as-equivalent: CL
+
+[Term]
+id: TEST:001
+xref: EXT:001
+xref: UBERON:001
+xref: CL:001
+is_a: EXT:001
+
+[Term]
+id: TEST:002
+xref: TEST:001
+xref: TEST:003
+
+[Term]
+id: TEST:003
+xref: EXT:002
+xref: EXT:002
+
+[Typedef]
+id: part_of
+xref: EXT:003
+xref: UBERON:002
+
+[Instance]
+id: TEST:004
+xref: UBERON:003
+",
+        )
+        .unwrap();
+
+        let borrowed = (&doc).into_graph().unwrap().graphs.remove(0);
+        let owned = doc.into_graph().unwrap().graphs.remove(0);
+        assert_eq!(triples(&borrowed), triples(&owned));
+        assert_eq!(borrowed.nodes, owned.nodes);
+        assert!(triples(&borrowed).contains(&(
+            "http://purl.obolibrary.org/obo/TEST_003",
+            "is_a",
+            "http://purl.obolibrary.org/obo/TEST_002"
+        )));
+    }
+
+    #[test]
+    fn frame() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let mut ctx = Context::from(&doc);
+        let borrowed: Graph = (&doc.entities()[0]).into_graph_ctx(&mut ctx).unwrap();
+        let owned: Graph = doc.entities()[0].clone().into_graph_ctx(&mut ctx).unwrap();
+        assert_eq!(borrowed, owned);
+    }
+
+    #[test]
+    fn treat_xrefs() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let g = (&doc).into_graph().unwrap().graphs.remove(0);
+        let edges = g
+            .edges
+            .iter()
+            .map(|e| (e.pred.as_str(), e.obj.as_str()))
+            .collect::<Vec<_>>();
+        assert!(edges.contains(&("is_a", "http://purl.obolibrary.org/obo/EXT_001")));
+        assert!(edges.contains(&(
+            "http://purl.obolibrary.org/obo/test.owl#part_of",
+            "http://purl.obolibrary.org/obo/UBERON_001"
+        )));
+    }
+}
+
+mod stream {
+    use super::*;
+
+    use std::fs::OpenOptions;
+    use std::io::BufReader;
+    use std::io::Cursor;
+
+    use fastobo::parser::Parser;
+    use fastobo::parser::SequentialParser;
+    use fastobo_graphs::into_graph::write_frames;
+
+    const OBO: &str = "format-version: 1.4
+ontology: test
+idspace: EXT http://example.com/ext/
+treat-xrefs-as-is_a: EXT
+
+[Term]
+id: TEST:001
+name: first term
+xref: EXT:001
+relationship: part_of TEST:002
+
+[Term]
+id: TEST:002
+name: second term
+equivalent_to: TEST:003
+
+[Typedef]
+id: part_of
+name: part of
+domain: TEST:001
+";
+
+    fn parser(obo: &str) -> SequentialParser<BufReader<&[u8]>> {
+        SequentialParser::new(BufReader::new(obo.as_bytes()))
+    }
+
+    #[test]
+    fn same_as_document() {
+        let doc = OboDoc::from_str(OBO).unwrap();
+        let expected = fastobo_graphs::to_string(&doc.into_graph().unwrap()).unwrap();
+        let spill = Cursor::new(Vec::new());
+        let actual = write_frames(parser(OBO), Vec::new(), spill).unwrap();
+        assert_eq!(String::from_utf8(actual).unwrap(), expected);
+    }
+
+    #[test]
+    fn file_spill() {
+        let path = std::env::temp_dir().join(format!("spill-{}.json", std::process::id()));
+        let spill = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+        let result = write_frames(parser(OBO), Vec::new(), spill);
+        std::fs::remove_file(&path).unwrap();
+
+        let json = result.unwrap();
+        let doc = fastobo_graphs::from_reader(json.as_slice()).unwrap();
+        assert_eq!(doc.graphs[0].nodes.len(), 3);
+        assert_eq!(doc.graphs[0].edges.len(), 2);
+        assert_eq!(doc.graphs[0].domain_range_axioms.len(), 1);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let obo = "[Term]\nid: TEST:001\nnot a clause\n";
+        let result = write_frames(parser(obo), Vec::new(), Cursor::new(Vec::new()));
+        assert!(result.is_err());
+    }
+}
+
+mod property_type {
+    use super::*;
+
+    use fastobo::ast::EntityFrame;
+    use fastobo::ast::TypedefClause;
+    use fastobo::ast::TypedefFrame;
+    use fastobo_graphs::model::PropertyType;
+
+    const OBO: &str = "ontology: test
+idspace: EXT http://example.com/ext/
+
+[Typedef]
+id: part_of
+
+[Typedef]
+id: has_weight
+range: xsd:decimal
+
+[Typedef]
+id: editor_note
+is_metadata_tag: true
+
+[Typedef]
+id: source
+is_class_level: true
+";
+
+    #[test]
+    fn into_graph() {
+        let g = graph(OBO);
+        let types = g
+            .nodes
+            .iter()
+            .map(|n| (n.id.rsplit('#').next().unwrap(), n.property_type))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                ("part_of", Some(PropertyType::Object)),
+                ("has_weight", Some(PropertyType::Data)),
+                ("editor_note", Some(PropertyType::Annotation)),
+                ("source", Some(PropertyType::Annotation)),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefixes() {
+        let doc = OboDoc::from_str(OBO).unwrap().into_graph().unwrap();
+        assert_eq!(doc.prefixes.len(), 1);
+        assert_eq!(doc.prefixes["EXT"], "http://example.com/ext/");
+    }
+
+    #[test]
+    fn from_graph() {
+        let mut g = graph(OBO);
+        for node in g.nodes.iter_mut() {
+            // drop the metadata so that only the property type is used
+            node.meta = None;
+        }
+        let doc = OboDoc::from_graph(g).unwrap();
+        let tags = doc
+            .entities()
+            .iter()
+            .filter_map(EntityFrame::as_typedef)
+            .filter(|frame| {
+                frame
+                    .iter()
+                    .any(|c| c.as_inner() == &TypedefClause::IsMetadataTag(true))
+            })
+            .count();
+        assert_eq!(tags, 2);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let doc = OboDoc::from_graph(graph(OBO)).unwrap();
+        let frames = doc
+            .entities()
+            .iter()
+            .filter_map(EntityFrame::as_typedef)
+            .collect::<Vec<_>>();
+        let has = |frame: &TypedefFrame, clause: TypedefClause| {
+            frame.iter().any(|c| c.as_inner() == &clause)
+        };
+
+        // class-level relations are not turned into metadata tags
+        let class_level = frames
+            .iter()
+            .filter(|&&f| has(f, TypedefClause::IsClassLevel(true)))
+            .collect::<Vec<_>>();
+        assert_eq!(class_level.len(), 1);
+        assert!(!class_level[0]
+            .iter()
+            .any(|c| matches!(c.as_inner(), TypedefClause::IsMetadataTag(_))));
+
+        let tags = frames
+            .iter()
+            .filter(|&&f| has(f, TypedefClause::IsMetadataTag(true)))
+            .count();
+        assert_eq!(tags, 1);
+    }
+}
+
+mod transitivity {
+    use super::*;
+
+    use fastobo::ast::EntityFrame;
+    use fastobo::ast::TypedefClause;
+
+    #[test]
+    fn roundtrip() {
+        let g = graph("ontology: test\n\n[Typedef]\nid: part_of\nis_transitive: true\n");
+        let pvs = &g.nodes[0].meta.as_ref().unwrap().basic_property_values;
+        assert_eq!(
+            pvs[0].pred,
+            "http://www.geneontology.org/formats/oboInOwl#is_transitive"
+        );
+        assert_eq!(pvs[0].val, "true");
+
+        let doc = OboDoc::from_graph(g).unwrap();
+        match &doc.entities()[0] {
+            EntityFrame::Typedef(frame) => assert!(frame
+                .iter()
+                .any(|line| line.as_inner() == &TypedefClause::IsTransitive(true))),
+            other => panic!("unexpected frame: {}", other),
+        }
+    }
+}
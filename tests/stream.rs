@@ -0,0 +1,219 @@
+extern crate fastobo_graphs;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use fastobo_graphs::model::Graph;
+use fastobo_graphs::model::GraphDocument;
+use fastobo_graphs::model::Verbose;
+use fastobo_graphs::stream::Event;
+use fastobo_graphs::stream::GraphReader;
+use fastobo_graphs::stream::GraphWriter;
+
+const JSON: &str = r#"{
+    "graphs": [
+        {
+            "id": "http://purl.obolibrary.org/obo/test.owl",
+            "version": 1.0,
+            "nodes": [
+                {"id": "http://purl.obolibrary.org/obo/TEST_001", "lbl": "a \"quoted\" [label]"},
+                {"id": "http://purl.obolibrary.org/obo/TEST_002", "type": "CLASS"}
+            ],
+            "edges": [{
+                "sub": "http://purl.obolibrary.org/obo/TEST_001",
+                "pred": "is_a",
+                "obj": "http://purl.obolibrary.org/obo/TEST_002"
+            }],
+            "equivalentNodesSets": null,
+            "propertyChainAxioms": [{
+                "predicateId": "http://purl.obolibrary.org/obo/BFO_0000050",
+                "chainPredicateIds": ["http://purl.obolibrary.org/obo/BFO_0000050"]
+            }],
+            "meta": null
+        },
+        {"id": "second", "lbl": "second graph"}
+    ],
+    "meta": {"comments": ["document"]}
+}"#;
+
+#[test]
+fn events() {
+    let events = GraphReader::new(JSON.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let kinds = events
+        .iter()
+        .map(|event| match event {
+            Event::StartGraph => "start",
+            Event::Id(_) => "id",
+            Event::Label(_) => "label",
+            Event::Meta(_) => "meta",
+            Event::Node(_) => "node",
+            Event::Edge(_) => "edge",
+            Event::PropertyChainAxiom(_) => "axiom",
+            Event::Extra(..) => "extra",
+            Event::EndGraph => "end",
+            Event::DocumentMeta(_) => "document meta",
+            _ => "other",
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            "start",
+            "id",
+            "extra",
+            "node",
+            "node",
+            "edge",
+            "axiom",
+            "end",
+            "start",
+            "id",
+            "label",
+            "end",
+            "document meta"
+        ]
+    );
+    assert!(matches!(
+        &events[3],
+        Event::Node(node) if node.label.as_deref() == Some("a \"quoted\" [label]")
+    ));
+}
+
+#[test]
+fn nodes() {
+    let nodes = GraphReader::new(JSON.as_bytes())
+        .nodes()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let document: GraphDocument = serde_json::from_str(JSON).unwrap();
+    assert_eq!(nodes, document.graphs[0].nodes);
+}
+
+#[test]
+fn edges() {
+    let edges = GraphReader::new(JSON.as_bytes())
+        .edges()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let document: GraphDocument = serde_json::from_str(JSON).unwrap();
+    assert_eq!(edges, document.graphs[0].edges);
+}
+
+#[test]
+fn file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join("issue20.json");
+    let document: GraphDocument = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+    let reader = GraphReader::new(BufReader::new(File::open(&path).unwrap()));
+    let nodes = reader.nodes().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(nodes.len(), document.graphs[0].nodes.len());
+}
+
+#[test]
+fn roundtrip() {
+    let document: GraphDocument = serde_json::from_str(JSON).unwrap();
+    let graph: Graph = document.graphs[0].clone();
+
+    let mut writer = GraphWriter::new(Vec::new(), graph.id.clone(), graph.meta.clone()).unwrap();
+    let mut extra = std::collections::BTreeMap::new();
+    for event in GraphReader::new(JSON.as_bytes()).take_while(|e| !matches!(e, Ok(Event::EndGraph)))
+    {
+        match event.unwrap() {
+            Event::Node(node) => writer.write_node(&node).unwrap(),
+            Event::Edge(edge) => writer.write_edge(&edge).unwrap(),
+            Event::Extra(key, value) => {
+                extra.insert(key, value);
+            }
+            Event::PropertyChainAxiom(pca) => {
+                let mut g = Graph {
+                    nodes: Vec::new(),
+                    edges: Vec::new(),
+                    id: String::new(),
+                    label: None,
+                    meta: None,
+                    equivalent_nodes_sets: Vec::new(),
+                    logical_definition_axioms: Vec::new(),
+                    domain_range_axioms: Vec::new(),
+                    property_chain_axioms: Vec::new(),
+                    extra: Default::default(),
+                };
+                g.property_chain_axioms.push(pca);
+                writer.write_graph(g).unwrap();
+            }
+            _ => (),
+        }
+    }
+    writer.set_extra(extra);
+    let json = writer.finish().unwrap();
+    let actual: GraphDocument = serde_json::from_slice(&json).unwrap();
+    assert_eq!(actual, GraphDocument::from(graph));
+}
+
+#[test]
+fn syntax_error() {
+    let mut reader = GraphReader::new(r#"{"graphs": [{"nodes": [{"id": 1}]}]}"#.as_bytes());
+    assert!(matches!(reader.next(), Some(Ok(Event::StartGraph))));
+    assert!(matches!(reader.next(), Some(Err(_))));
+    assert!(reader.next().is_none());
+
+    let mut reader = GraphReader::new(r#"{"graphs": [{"nodes": [{"id": "A"}"#.as_bytes());
+    assert!(matches!(reader.next(), Some(Ok(Event::StartGraph))));
+    assert!(matches!(reader.next(), Some(Ok(Event::Node(_)))));
+    assert!(matches!(reader.next(), Some(Err(_))));
+}
+
+#[test]
+fn writer_verbose() {
+    let document: GraphDocument = serde_json::from_str(JSON).unwrap();
+    for graph in document.graphs.iter() {
+        let expected = GraphDocument::from(graph.clone());
+        for &verbose in &[false, true] {
+            let mut writer =
+                GraphWriter::new(Vec::new(), graph.id.clone(), graph.meta.clone()).unwrap();
+            writer.set_verbose(verbose);
+            writer.set_label(graph.label.clone());
+            writer.set_extra(graph.extra.clone());
+            writer.write_graph(graph.clone()).unwrap();
+            let json = String::from_utf8(writer.finish().unwrap()).unwrap();
+            if verbose {
+                assert_eq!(json, serde_json::to_string(&Verbose(&expected)).unwrap());
+            } else {
+                assert_eq!(json, fastobo_graphs::to_string(&expected).unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn prefixes_roundtrip() {
+    let json = r#"{
+        "graphs": [{"id": "test", "nodes": [{"id": "TEST:1"}]}],
+        "prefixes": {"TEST": "http://purl.obolibrary.org/obo/TEST_"},
+        "x-document": [1, 2]
+    }"#;
+    let document: GraphDocument = serde_json::from_str(json).unwrap();
+
+    let mut writer = GraphWriter::new(Vec::new(), String::from("test"), None).unwrap();
+    let mut extra = Vec::new();
+    for event in GraphReader::new(json.as_bytes()) {
+        match event.unwrap() {
+            Event::Node(node) => writer.write_node(&node).unwrap(),
+            Event::DocumentPrefixes(prefixes) => writer.set_prefixes(prefixes),
+            Event::DocumentExtra(key, value) => extra.push((key, value)),
+            _ => (),
+        }
+    }
+    assert_eq!(
+        extra,
+        vec![(String::from("x-document"), serde_json::json!([1, 2]))]
+    );
+
+    let actual: GraphDocument = serde_json::from_slice(&writer.finish().unwrap()).unwrap();
+    assert_eq!(actual.prefixes, document.prefixes);
+    assert_eq!(actual.graphs, document.graphs);
+}
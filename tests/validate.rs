@@ -0,0 +1,217 @@
+extern crate fastobo_graphs;
+
+use fastobo_graphs::model::GraphDocument;
+use fastobo_graphs::validate::validate;
+use fastobo_graphs::validate::Check;
+use fastobo_graphs::validate::Severity;
+
+const OBO_IN_OWL: &str = "http://www.geneontology.org/formats/oboInOwl#";
+
+fn document(nodes: &str, edges: &str, axioms: &str) -> GraphDocument {
+    let json = format!(
+        r#"{{"graphs": [{{
+            "id": "http://purl.obolibrary.org/obo/test.owl",
+            "nodes": [{}],
+            "edges": [{}],
+            "domainRangeAxioms": [{}]
+        }}]}}"#,
+        nodes, edges, axioms
+    );
+    fastobo_graphs::from_str(json).unwrap()
+}
+
+fn ids(doc: &GraphDocument, check: Check) -> Vec<String> {
+    validate(doc)
+        .with_check(check)
+        .map(|f| f.id.clone())
+        .collect()
+}
+
+#[test]
+fn valid() {
+    let doc = document(
+        r#"
+        {"id": "A", "lbl": "a", "type": "CLASS"},
+        {"id": "B", "lbl": "b", "type": "CLASS", "meta": {
+            "definition": {"val": "A b."},
+            "synonyms": [{"pred": "hasExactSynonym", "val": "bee"}]
+        }},
+        {"id": "R", "lbl": "r", "type": "PROPERTY"}
+        "#,
+        r#"{"sub": "A", "pred": "is_a", "obj": "B"}"#,
+        r#"{"predicateId": "R", "domainClassIds": ["A"]}"#,
+    );
+    let report = validate(&doc);
+    assert!(report.is_empty(), "{}", report);
+    assert_eq!(report.max_severity(), None);
+}
+
+#[test]
+fn dangling_edge() {
+    let doc = document(
+        r#"{"id": "A", "lbl": "a"}"#,
+        r#"{"sub": "A", "pred": "is_a", "obj": "B"}"#,
+        "",
+    );
+    let report = validate(&doc);
+    assert_eq!(report.len(), 1);
+    assert_eq!(report.findings()[0].check, Check::DanglingEdge);
+    assert_eq!(report.findings()[0].severity, Severity::Error);
+    assert_eq!(report.findings()[0].id, "B");
+    assert!(report.has_errors());
+}
+
+#[test]
+fn duplicate_id() {
+    let doc = document(
+        r#"{"id": "A", "lbl": "a"}, {"id": "A", "lbl": "a"}"#,
+        "",
+        "",
+    );
+    assert_eq!(ids(&doc, Check::DuplicateId), vec!["A"]);
+}
+
+#[test]
+fn missing_label() {
+    let doc = document(r#"{"id": "A", "lbl": "a"}, {"id": "B"}"#, "", "");
+    assert_eq!(ids(&doc, Check::MissingLabel), vec!["B"]);
+}
+
+#[test]
+fn duplicate_label() {
+    let nodes = format!(
+        r#"
+        {{"id": "A", "lbl": "x"}},
+        {{"id": "B", "lbl": "x"}},
+        {{"id": "C", "lbl": "x", "meta": {{"basicPropertyValues": [
+            {{"pred": "{0}hasOBONamespace", "val": "other"}}
+        ]}}}},
+        {{"id": "D", "lbl": "x", "meta": {{"deprecated": true, "basicPropertyValues": [
+            {{"pred": "{0}replacedBy", "val": "A"}}
+        ]}}}}
+        "#,
+        OBO_IN_OWL
+    );
+    let doc = document(&nodes, "", "");
+    assert_eq!(ids(&doc, Check::DuplicateLabel), vec!["A", "B"]);
+}
+
+#[test]
+fn definitions() {
+    let doc = document(
+        r#"
+        {"id": "A", "lbl": "a", "meta": {
+            "definition": {"val": "An a."},
+            "basicPropertyValues": [
+                {"pred": "http://purl.obolibrary.org/obo/IAO_0000115", "val": "Another a."}
+            ]
+        }},
+        {"id": "B", "lbl": "b", "meta": {"definition": {"val": " "}}},
+        {"id": "C", "lbl": "c", "meta": {"basicPropertyValues": [
+            {"pred": "http://purl.obolibrary.org/obo/IAO_0000115", "val": ""}
+        ]}},
+        {"id": "D", "lbl": "d", "meta": {"basicPropertyValues": [
+            {"pred": "http://www.geneontology.org/formats/oboInOwl#hasDefinition", "val": "\t"}
+        ]}}
+        "#,
+        "",
+        "",
+    );
+    assert_eq!(ids(&doc, Check::MultipleDefinitions), vec!["A"]);
+    assert_eq!(ids(&doc, Check::EmptyDefinition), vec!["B", "C", "D"]);
+    assert_eq!(validate(&doc).max_severity(), Some(Severity::Error));
+}
+
+#[test]
+fn deprecated() {
+    let doc = document(
+        r#"
+        {"id": "A", "lbl": "a"},
+        {"id": "B", "lbl": "b", "meta": {"deprecated": true}},
+        {"id": "C", "lbl": "c", "meta": {"deprecated": true, "basicPropertyValues": [
+            {"pred": "http://purl.obolibrary.org/obo/IAO_0100001", "val": "A"}
+        ]}}
+        "#,
+        r#"{"sub": "A", "pred": "is_a", "obj": "B"}"#,
+        "",
+    );
+    let report = validate(&doc);
+    assert_eq!(ids(&doc, Check::DeprecatedReference), vec!["B"]);
+    assert_eq!(ids(&doc, Check::MissingReplacement), vec!["B"]);
+    assert_eq!(
+        report
+            .with_severity(Severity::Warning)
+            .map(|f| f.check)
+            .collect::<Vec<_>>(),
+        vec![Check::MissingReplacement]
+    );
+}
+
+#[test]
+fn synonym_type() {
+    let nodes = format!(
+        r#"{{"id": "A", "lbl": "a", "meta": {{"synonyms": [
+            {{"pred": "{}hasBroadSynonym", "val": "x"}},
+            {{"pred": "hasWeirdSynonym", "val": "y"}}
+        ]}}}}"#,
+        OBO_IN_OWL
+    );
+    let doc = document(&nodes, "", "");
+    assert_eq!(ids(&doc, Check::InvalidSynonymType), vec!["A"]);
+}
+
+#[test]
+fn domain_range() {
+    let doc = document(
+        r#"
+        {"id": "A", "lbl": "a", "type": "CLASS"},
+        {"id": "R", "lbl": "r", "type": "PROPERTY"}
+        "#,
+        "",
+        r#"
+        {"predicateId": "A", "domainClassIds": ["A"]},
+        {"predicateId": "R", "rangeClassIds": ["A"]},
+        {"predicateId": "S", "rangeClassIds": ["A"]}
+        "#,
+    );
+    assert_eq!(ids(&doc, Check::InvalidDomainRange), vec!["A", "S"]);
+}
+
+#[test]
+fn order() {
+    let doc = document(
+        r#"
+        {"id": "A", "lbl": "x"},
+        {"id": "B"},
+        {"id": "C", "lbl": "x"},
+        {"id": "D"}
+        "#,
+        r#"{"sub": "E", "pred": "is_a", "obj": "A"}"#,
+        "",
+    );
+    let report = validate(&doc);
+    let findings = report
+        .findings()
+        .iter()
+        .map(|f| (f.check, f.id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        vec![
+            (Check::DanglingEdge, "E"),
+            (Check::MissingLabel, "B"),
+            (Check::MissingLabel, "D"),
+            (Check::DuplicateLabel, "A"),
+            (Check::DuplicateLabel, "C"),
+        ]
+    );
+}
+
+#[test]
+fn display() {
+    let doc = document(r#"{"id": "A"}"#, "", "");
+    assert_eq!(
+        validate(&doc).to_string(),
+        "ERROR\tmissing_label\tA\tnode has no label\n"
+    );
+}